@@ -0,0 +1,119 @@
+use std::{fmt, ptr, sync::Arc};
+
+use windows::{
+    core::Interface,
+    Win32::System::Com::{
+        CoCreateInstance, IDispatch, IGlobalInterfaceTable, StdGlobalInterfaceTable,
+        CLSCTX_INPROC_SERVER,
+    },
+};
+
+use crate::{apartment::ensure_apartment, error::Result, OleData};
+
+/// A `Send + Sync` handle to an [`OleData`] that can be moved to other threads.
+///
+/// The object's `IDispatch` is registered in the process-wide Global Interface
+/// Table; [`AgileOleData::resolve`] returns an [`OleData`] usable in the calling
+/// thread's apartment, which is a proxy unless that is the apartment the object
+/// lives in. Clones share the registration, which is revoked by
+/// [`AgileOleData::revoke`] or [`AgileOleData::into_local`] on the last clone,
+/// or when the last clone is dropped.
+#[derive(Clone)]
+pub struct AgileOleData {
+    registration: Arc<Registration>,
+}
+
+struct Registration {
+    cookie: u32,
+    revoked: bool,
+}
+
+impl AgileOleData {
+    pub fn new(object: &OleData) -> Result<AgileOleData> {
+        ensure_apartment()?;
+        let cookie = unsafe {
+            global_interface_table()?
+                .RegisterInterfaceInGlobal(&object.dispatch, &IDispatch::IID)?
+        };
+        Ok(AgileOleData {
+            registration: Arc::new(Registration {
+                cookie,
+                revoked: false,
+            }),
+        })
+    }
+    /// Unmarshal the object into the current thread's apartment.
+    ///
+    #[track_caller]
+    pub fn resolve(&self) -> Result<OleData> {
+        ensure_apartment()?;
+        let mut dispatch = ptr::null_mut();
+        unsafe {
+            global_interface_table()?.GetInterfaceFromGlobal(
+                self.registration.cookie,
+                &IDispatch::IID,
+                &mut dispatch,
+            )?
+        };
+        Ok(OleData::from_dispatch(unsafe {
+            IDispatch::from_raw(dispatch)
+        }))
+    }
+    /// Release this handle, revoking the registration if it is the last clone.
+    ///
+    /// Unlike dropping the handle, a failure to revoke is reported. While other
+    /// clones are alive the registration stays in place for them.
+    ///
+    pub fn revoke(self) -> Result<()> {
+        match Arc::try_unwrap(self.registration) {
+            Ok(mut registration) => registration.revoke(),
+            Err(_) => Ok(()),
+        }
+    }
+    /// Unmarshal the object into the current thread's apartment and release
+    /// this handle, see [`AgileOleData::revoke`].
+    ///
+    #[track_caller]
+    pub fn into_local(self) -> Result<OleData> {
+        let object = self.resolve()?;
+        self.revoke()?;
+        Ok(object)
+    }
+    /// The Global Interface Table cookie of the registration.
+    ///
+    pub fn cookie(&self) -> u32 {
+        self.registration.cookie
+    }
+}
+
+impl Registration {
+    // Only ever attempted once, even if it fails.
+    fn revoke(&mut self) -> Result<()> {
+        self.revoked = true;
+        ensure_apartment()?;
+        unsafe { global_interface_table()?.RevokeInterfaceFromGlobal(self.cookie)? };
+        Ok(())
+    }
+}
+
+impl Drop for Registration {
+    // Revoking needs COM on the dropping thread; if it is not initialized the
+    // registration is left in place until the process exits.
+    fn drop(&mut self) {
+        if !self.revoked {
+            let _ = self.revoke();
+        }
+    }
+}
+
+impl fmt::Debug for AgileOleData {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("AgileOleData")
+            .field("cookie", &self.registration.cookie)
+            .finish()
+    }
+}
+
+fn global_interface_table() -> Result<IGlobalInterfaceTable> {
+    Ok(unsafe { CoCreateInstance(&StdGlobalInterfaceTable, None, CLSCTX_INPROC_SERVER)? })
+}
@@ -0,0 +1,232 @@
+use std::{cell::Cell, marker::PhantomData};
+
+use windows::Win32::{
+    Foundation::{CO_E_NOTINITIALIZED, RPC_E_CHANGED_MODE},
+    System::{
+        Com::{
+            CoGetApartmentType, CoInitializeEx, CoUninitialize, APTTYPE, APTTYPEQUALIFIER,
+            APTTYPEQUALIFIER_IMPLICIT_MTA, APTTYPE_MAINSTA, APTTYPE_MTA, APTTYPE_NA, APTTYPE_STA,
+            COINIT, COINIT_APARTMENTTHREADED, COINIT_DISABLE_OLE1DDE, COINIT_MULTITHREADED,
+            COINIT_SPEED_OVER_MEMORY,
+        },
+        Ole::{OleInitialize, OleUninitialize},
+    },
+};
+
+use crate::{
+    error::{Error, OleError, Result},
+    G_RUNNING_NANO,
+};
+
+thread_local! {
+    /// The apartment entered by the live [`Apartment`] guards of this thread,
+    /// and how many of them there are.
+    static APARTMENT: Cell<Option<(ApartmentType, usize)>> = const { Cell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApartmentType {
+    /// Single-threaded apartment (STA), required for most automation servers
+    /// and for OLE drag and drop or clipboard access.
+    SingleThreaded,
+    /// The process-wide multithreaded apartment (MTA).
+    MultiThreaded,
+    /// The neutral apartment; only reported by [`current_apartment`].
+    Neutral,
+}
+
+/// RAII guard for COM initialization on the current thread.
+///
+/// COM stays initialized until the guard is dropped. Every [`OleData`](crate::OleData)
+/// and other COM object created on the thread must be dropped before it.
+/// The guard cannot be sent to another thread, since COM initialization is
+/// per-thread.
+#[derive(Debug)]
+pub struct Apartment {
+    apartment_type: ApartmentType,
+    ole: bool,
+    _ptr: PhantomData<*mut ()>,
+}
+
+impl Apartment {
+    /// Enter a single-threaded apartment without OLE.
+    ///
+    pub fn sta() -> Result<Apartment> {
+        ApartmentBuilder::new(ApartmentType::SingleThreaded).init()
+    }
+    /// Enter the multithreaded apartment.
+    ///
+    pub fn mta() -> Result<Apartment> {
+        ApartmentBuilder::new(ApartmentType::MultiThreaded).init()
+    }
+    /// Enter a single-threaded apartment through `OleInitialize`, which also
+    /// enables drag and drop, the clipboard and in-place activation.
+    ///
+    pub fn ole() -> Result<Apartment> {
+        ApartmentBuilder::new(ApartmentType::SingleThreaded)
+            .ole(true)
+            .init()
+    }
+    pub fn builder(apartment_type: ApartmentType) -> ApartmentBuilder {
+        ApartmentBuilder::new(apartment_type)
+    }
+    pub fn apartment_type(&self) -> ApartmentType {
+        self.apartment_type
+    }
+    pub fn is_ole(&self) -> bool {
+        self.ole
+    }
+}
+
+impl Drop for Apartment {
+    #[inline]
+    fn drop(&mut self) {
+        APARTMENT.set(match APARTMENT.get() {
+            Some((apartment_type, count)) if count > 1 => Some((apartment_type, count - 1)),
+            _ => None,
+        });
+        if self.ole {
+            unsafe { OleUninitialize() };
+        } else {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Options for entering an [`Apartment`].
+#[derive(Debug, Clone, Copy)]
+pub struct ApartmentBuilder {
+    apartment_type: ApartmentType,
+    ole: bool,
+    flags: COINIT,
+}
+
+impl ApartmentBuilder {
+    pub fn new(apartment_type: ApartmentType) -> ApartmentBuilder {
+        ApartmentBuilder {
+            apartment_type,
+            ole: false,
+            flags: COINIT(0),
+        }
+    }
+    /// Initialize through `OleInitialize`. Only valid for a single-threaded
+    /// apartment; not available on Nano Server.
+    ///
+    pub fn ole(mut self, ole: bool) -> ApartmentBuilder {
+        self.ole = ole;
+        self
+    }
+    /// Pass `COINIT_DISABLE_OLE1DDE`.
+    ///
+    pub fn disable_ole1dde(mut self, disable: bool) -> ApartmentBuilder {
+        self.set_flag(COINIT_DISABLE_OLE1DDE, disable);
+        self
+    }
+    /// Pass `COINIT_SPEED_OVER_MEMORY`.
+    ///
+    pub fn speed_over_memory(mut self, speed: bool) -> ApartmentBuilder {
+        self.set_flag(COINIT_SPEED_OVER_MEMORY, speed);
+        self
+    }
+    fn set_flag(&mut self, flag: COINIT, on: bool) {
+        if on {
+            self.flags.0 |= flag.0;
+        } else {
+            self.flags.0 &= !flag.0;
+        }
+    }
+    /// Initialize COM on the current thread.
+    ///
+    /// Fails if the thread is already in an apartment of a different type.
+    /// Initializing again with the same type succeeds and must be balanced by
+    /// dropping the returned guard like any other.
+    ///
+    pub fn init(self) -> Result<Apartment> {
+        let result = match self.apartment_type {
+            ApartmentType::SingleThreaded if self.ole => {
+                if *G_RUNNING_NANO {
+                    return Err(Error::Generic("OLE is not available on Nano Server"));
+                }
+                unsafe { OleInitialize(None) }
+            }
+            ApartmentType::SingleThreaded => unsafe {
+                CoInitializeEx(None, COINIT_APARTMENTTHREADED | self.flags).ok()
+            },
+            _ if self.ole => {
+                return Err(Error::Generic(
+                    "OLE can only be initialized in a single-threaded apartment",
+                ))
+            }
+            ApartmentType::MultiThreaded => unsafe {
+                CoInitializeEx(None, COINIT_MULTITHREADED | self.flags).ok()
+            },
+            ApartmentType::Neutral => {
+                return Err(Error::Generic(
+                    "a thread cannot enter the neutral apartment",
+                ))
+            }
+        };
+        match result {
+            Ok(()) => {
+                let count = APARTMENT.get().map_or(0, |(_, count)| count);
+                APARTMENT.set(Some((self.apartment_type, count + 1)));
+                Ok(Apartment {
+                    apartment_type: self.apartment_type,
+                    ole: self.ole,
+                    _ptr: PhantomData,
+                })
+            }
+            Err(error) if error.code() == RPC_E_CHANGED_MODE => Err(OleError::runtime(
+                error,
+                "thread is already initialized with a different apartment type",
+            )
+            .into()),
+            Err(error) => Err(OleError::runtime(error, "failed: OLE initialization").into()),
+        }
+    }
+}
+
+/// The apartment the current thread belongs to, or `None` if COM has not been
+/// initialized on it.
+///
+/// Threads that never initialized COM report the multithreaded apartment
+/// while the process has one, e.g. after [`init_runtime`](crate::init_runtime).
+pub fn current_apartment() -> Result<Option<ApartmentType>> {
+    let mut apt_type = APTTYPE::default();
+    let mut qualifier = APTTYPEQUALIFIER::default();
+    match unsafe { CoGetApartmentType(&mut apt_type, &mut qualifier) } {
+        Ok(()) => Ok(Some(match apt_type {
+            APTTYPE_STA | APTTYPE_MAINSTA => ApartmentType::SingleThreaded,
+            APTTYPE_MTA => ApartmentType::MultiThreaded,
+            APTTYPE_NA => ApartmentType::Neutral,
+            _ => return Ok(None),
+        })),
+        Err(error) if error.code() == CO_E_NOTINITIALIZED => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Fail unless COM is initialized on the current thread.
+///
+/// An [`Apartment`] guard alive on the thread answers this without asking COM.
+/// Otherwise the thread may have been initialized by its host, which
+/// `CoGetApartmentType` tells. Being in the MTA only implicitly, e.g. after
+/// [`init_runtime`](crate::init_runtime), does not count: the thread has not
+/// initialized COM itself.
+pub(crate) fn ensure_apartment() -> Result<()> {
+    if APARTMENT.get().is_some() {
+        return Ok(());
+    }
+    let mut apt_type = APTTYPE::default();
+    let mut qualifier = APTTYPEQUALIFIER::default();
+    match unsafe { CoGetApartmentType(&mut apt_type, &mut qualifier) } {
+        Ok(()) if qualifier != APTTYPEQUALIFIER_IMPLICIT_MTA => Ok(()),
+        Ok(()) => Err(not_initialized()),
+        Err(error) if error.code() == CO_E_NOTINITIALIZED => Err(not_initialized()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn not_initialized() -> Error {
+    Error::Generic("COM is not initialized on this thread; create an Apartment first")
+}
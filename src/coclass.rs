@@ -0,0 +1,125 @@
+use windows::{
+    core::{Interface, GUID},
+    Win32::{
+        Globalization::GetUserDefaultLCID,
+        System::{
+            Com::{IPersist, ITypeInfo, TKIND_COCLASS},
+            Ole::{IProvideClassInfo, IProvideClassInfo2, GUIDKIND_DEFAULT_SOURCE_DISP_IID},
+            Registry::HKEY_CLASSES_ROOT,
+        },
+    },
+};
+
+use crate::{
+    error::{Error, Result},
+    oletypelibdata::oletypelib_from_guid,
+    types::TypeInfos,
+    util::{ole::TypeRef, RegKey},
+    OleData, OleTypeData,
+};
+
+/// Find the coclass of a live object.
+///
+/// `IProvideClassInfo` is asked first. Otherwise the object's CLSID, from
+/// creation or `IPersist::GetClassID`, is looked up in the registry to load
+/// its type library. As a last resort the library containing the object's
+/// dispatch interface is searched for a coclass with it as default interface.
+pub(crate) fn ole_coclass(object: &OleData) -> Result<OleTypeData> {
+    if let Ok(provider) = object.dispatch.cast::<IProvideClassInfo>() {
+        if let Ok(typeinfo) = unsafe { provider.GetClassInfo() } {
+            return OleTypeData::try_from(typeinfo);
+        }
+    }
+    if let Some(clsid) = object_clsid(object) {
+        if let Ok(Some(typeinfo)) = coclass_from_registry(&clsid) {
+            return OleTypeData::try_from(typeinfo);
+        }
+    }
+    let typeinfo = unsafe { object.dispatch.GetTypeInfo(0, GetUserDefaultLCID())? };
+    match coclass_from_default_interface(&typeinfo)? {
+        Some(typeinfo) => OleTypeData::try_from(typeinfo),
+        None => Err(Error::Generic("failed to find the coclass of the object")),
+    }
+}
+
+/// The IID of the object's default source dispinterface, i.e. the events it
+/// raises, or `None` if it does not source any.
+pub(crate) fn default_source_iid(object: &OleData) -> Result<Option<GUID>> {
+    if let Ok(provider) = object.dispatch.cast::<IProvideClassInfo2>() {
+        if let Ok(iid) = unsafe { provider.GetGUID(GUIDKIND_DEFAULT_SOURCE_DISP_IID.0 as u32) } {
+            return Ok(Some(iid));
+        }
+    }
+    let coclass = ole_coclass(object)?;
+    Ok(coclass
+        .default_event_sources()?
+        .first()
+        .map(|source| source.guid()))
+}
+
+fn object_clsid(object: &OleData) -> Option<GUID> {
+    object.clsid().or_else(|| {
+        let persist = object.dispatch.cast::<IPersist>().ok()?;
+        unsafe { persist.GetClassID() }.ok()
+    })
+}
+
+/// Load the type library registered for `clsid` and return its coclass.
+fn coclass_from_registry(clsid: &GUID) -> Result<Option<ITypeInfo>> {
+    let hclsid = RegKey::predef(HKEY_CLASSES_ROOT).open_subkey(format!("CLSID\\{{{clsid:?}}}"))?;
+    let libid: String = hclsid.open_subkey("TypeLib")?.get_value("")?;
+    let version = match hclsid
+        .open_subkey("Version")
+        .and_then(|key| key.get_value(""))
+    {
+        Ok(version) => version,
+        Err(_) => {
+            let hlibid =
+                RegKey::predef(HKEY_CLASSES_ROOT).open_subkey(format!("TypeLib\\{libid}"))?;
+            let mut versions = hlibid.enum_keys().collect::<Result<Vec<String>>>()?;
+            versions.sort_by_key(|version| version_key(version));
+            match versions.pop() {
+                Some(version) => version,
+                None => return Ok(None),
+            }
+        }
+    };
+    let typelib = oletypelib_from_guid(&libid, &version)?;
+    Ok(unsafe { typelib.GetTypeInfoOfGuid(clsid) }.ok())
+}
+
+/// Registry type library versions are hexadecimal `major.minor`.
+fn version_key(version: &str) -> (u32, u32) {
+    let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+    (
+        u32::from_str_radix(major, 16).unwrap_or(0),
+        u32::from_str_radix(minor, 16).unwrap_or(0),
+    )
+}
+
+/// Search the library containing `interface` for a coclass whose default
+/// interface it is.
+fn coclass_from_default_interface(interface: &ITypeInfo) -> Result<Option<ITypeInfo>> {
+    let type_attr = unsafe { interface.GetTypeAttr()? };
+    let guid = unsafe { (*type_attr).guid };
+    unsafe { interface.ReleaseTypeAttr(type_attr) };
+
+    let mut typelib = None;
+    let mut index = 0;
+    unsafe { interface.GetContainingTypeLib(&mut typelib, &mut index)? };
+    let typelib = typelib.unwrap();
+    for typeinfo in TypeInfos::from(&typelib) {
+        let typeinfo = OleTypeData::try_from(typeinfo?)?;
+        if typeinfo.typekind() != TKIND_COCLASS {
+            continue;
+        }
+        let implements = typeinfo
+            .default_ole_types()?
+            .iter()
+            .any(|interface| interface.guid() == guid);
+        if implements {
+            return Ok(Some(typeinfo.typeinfo().clone()));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use windows::{
+    core::GUID,
+    Win32::System::{
+        Com::{ITypeInfo, ITypeLib, CUSTDATA},
+        Ole::ClearCustData,
+    },
+};
+
+use crate::{
+    error::Result,
+    olevalue::OleValue,
+    util::ole::{typeinfo2, typelib2},
+};
+
+/// Custom data attached through IDL `[custom(GUID, value)]` attributes, keyed
+/// by the attribute GUID.
+pub type CustomData = HashMap<GUID, OleValue>;
+
+fn custom_data_from_raw(mut custdata: CUSTDATA) -> Result<CustomData> {
+    let mut items = CustomData::with_capacity(custdata.cCustData as usize);
+    let mut result = Ok(());
+    for i in 0..custdata.cCustData as usize {
+        let item = unsafe { &*custdata.prgCustData.add(i) };
+        match OleValue::try_from(&item.varValue) {
+            Ok(value) => {
+                items.insert(item.guid, value);
+            }
+            Err(error) => {
+                result = Err(error);
+                break;
+            }
+        }
+    }
+    unsafe { ClearCustData(&mut custdata) };
+    result.map(|()| items)
+}
+
+pub(crate) fn typelib_custom_data(typelib: &ITypeLib) -> Result<CustomData> {
+    let typelib2 = typelib2(typelib)?;
+    let custdata = unsafe { typelib2.GetAllCustData()? };
+    custom_data_from_raw(custdata)
+}
+
+pub(crate) fn type_custom_data(typeinfo: &ITypeInfo) -> Result<CustomData> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let custdata = unsafe { typeinfo2.GetAllCustData()? };
+    custom_data_from_raw(custdata)
+}
+
+pub(crate) fn func_custom_data(typeinfo: &ITypeInfo, index: u32) -> Result<CustomData> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let custdata = unsafe { typeinfo2.GetAllFuncCustData(index)? };
+    custom_data_from_raw(custdata)
+}
+
+pub(crate) fn param_custom_data(
+    typeinfo: &ITypeInfo,
+    func_index: u32,
+    param_index: u32,
+) -> Result<CustomData> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let custdata = unsafe { typeinfo2.GetAllParamCustData(func_index, param_index)? };
+    custom_data_from_raw(custdata)
+}
+
+pub(crate) fn var_custom_data(typeinfo: &ITypeInfo, memid: i32) -> Result<CustomData> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let index = unsafe { typeinfo2.GetVarIndexOfMemId(memid)? };
+    let custdata = unsafe { typeinfo2.GetAllVarCustData(index)? };
+    custom_data_from_raw(custdata)
+}
+
+pub(crate) fn impl_type_custom_data(typeinfo: &ITypeInfo, index: u32) -> Result<CustomData> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let custdata = unsafe { typeinfo2.GetAllImplTypeCustData(index)? };
+    custom_data_from_raw(custdata)
+}
+
+/// Format custom data in the syntax of IDL `custom` attributes, e.g.
+/// `custom(0F21F359-AB84-41E8-9A78-36D110E6D2F9, "Excel.Range")`, for the
+/// `idl_attributes` methods. Attributes are sorted by GUID so the output is
+/// stable between runs.
+pub(crate) fn format_custom_attributes(custom_data: &CustomData) -> Vec<String> {
+    let mut attributes: Vec<String> = custom_data
+        .iter()
+        .map(|(guid, value)| format!("custom({guid:?}, {})", idl_literal(value)))
+        .collect();
+    attributes.sort();
+    attributes
+}
+
+fn idl_literal(value: &OleValue) -> String {
+    match value {
+        OleValue::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+        OleValue::Bool(true) => "TRUE".into(),
+        OleValue::Bool(false) => "FALSE".into(),
+        OleValue::Empty | OleValue::Null => "0".into(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,265 @@
+use std::{collections::HashMap, ptr};
+
+use windows::{
+    core::{BSTR, GUID, HRESULT},
+    Win32::{
+        Foundation::E_FAIL,
+        System::{
+            Com::{ITypeInfo, ITypeLib, TKIND_ALIAS, TYPEDESC},
+            Variant::{VT_CARRAY, VT_PTR, VT_SAFEARRAY, VT_USERDEFINED},
+        },
+    },
+};
+
+use crate::{
+    error::{Error, Result},
+    types::TypeInfos,
+};
+
+/// Where in the referencing type an external type is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeRefKind {
+    Param,
+    Return,
+    Field,
+    ImplType,
+    Alias,
+}
+
+/// A reference from a type in the analysed library to a type in another one.
+#[derive(Debug, Clone)]
+pub struct ExternalTypeRef {
+    pub kind: TypeRefKind,
+    /// Name of the referencing type in the analysed library.
+    pub from_type: String,
+    /// Name of the referencing method or field, if any.
+    pub member: Option<String>,
+    /// Name of the referenced type.
+    pub type_name: String,
+}
+
+/// An external type library together with every reference made to it.
+#[derive(Debug, Clone)]
+pub struct LibraryDependency {
+    pub guid: GUID,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub lcid: u32,
+    pub name: String,
+    pub references: Vec<ExternalTypeRef>,
+}
+
+/// A type reference that `GetRefTypeInfo` could not resolve, usually because
+/// the library declaring it is not registered.
+#[derive(Debug, Clone)]
+pub struct UnresolvedTypeRef {
+    pub kind: TypeRefKind,
+    pub from_type: String,
+    pub member: Option<String>,
+    pub href: u32,
+    pub hresult: HRESULT,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TypeLibDependencies {
+    pub libraries: Vec<LibraryDependency>,
+    pub unresolved: Vec<UnresolvedTypeRef>,
+}
+
+struct PendingRef {
+    kind: TypeRefKind,
+    from_type: String,
+    member: Option<String>,
+    href: u32,
+}
+
+type LibraryKey = (GUID, u16, u16, u32);
+
+pub(crate) fn typelib_dependencies(typelib: &ITypeLib) -> Result<TypeLibDependencies> {
+    let tlib_attr = unsafe { typelib.GetLibAttr()? };
+    let own_guid = unsafe { (*tlib_attr).guid };
+    unsafe { typelib.ReleaseTLibAttr(tlib_attr) };
+
+    let mut libraries: HashMap<LibraryKey, LibraryDependency> = HashMap::new();
+    let mut unresolved = vec![];
+
+    for typeinfo in TypeInfos::from(typelib) {
+        let typeinfo = typeinfo?;
+        for pending in type_references(&typeinfo)? {
+            let reftypeinfo = match unsafe { typeinfo.GetRefTypeInfo(pending.href) } {
+                Ok(reftypeinfo) => reftypeinfo,
+                Err(error) => {
+                    unresolved.push(UnresolvedTypeRef {
+                        kind: pending.kind,
+                        from_type: pending.from_type,
+                        member: pending.member,
+                        href: pending.href,
+                        hresult: error.code(),
+                    });
+                    continue;
+                }
+            };
+            let (key, library_name, type_name) = match referenced_library(&reftypeinfo) {
+                Ok(library) => library,
+                Err(error) => {
+                    unresolved.push(UnresolvedTypeRef {
+                        kind: pending.kind,
+                        from_type: pending.from_type,
+                        member: pending.member,
+                        href: pending.href,
+                        hresult: error.hresult().map_or(E_FAIL, HRESULT::from),
+                    });
+                    continue;
+                }
+            };
+            if key.0 == own_guid {
+                continue;
+            }
+
+            let reference = ExternalTypeRef {
+                kind: pending.kind,
+                from_type: pending.from_type,
+                member: pending.member,
+                type_name,
+            };
+            libraries
+                .entry(key)
+                .or_insert_with(|| LibraryDependency {
+                    guid: key.0,
+                    major_version: key.1,
+                    minor_version: key.2,
+                    lcid: key.3,
+                    name: library_name,
+                    references: vec![],
+                })
+                .references
+                .push(reference);
+        }
+    }
+
+    let mut libraries: Vec<LibraryDependency> = libraries.into_values().collect();
+    libraries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(TypeLibDependencies {
+        libraries,
+        unresolved,
+    })
+}
+
+/// The library containing `reftypeinfo`, its name, and the name of the type.
+fn referenced_library(reftypeinfo: &ITypeInfo) -> Result<(LibraryKey, String, String)> {
+    let mut reftypelib = None;
+    let mut index = 0;
+    unsafe { reftypeinfo.GetContainingTypeLib(&mut reftypelib, &mut index)? };
+    let reftypelib = reftypelib.ok_or(Error::Generic(
+        "referenced type has no containing type library",
+    ))?;
+    let ref_attr = unsafe { reftypelib.GetLibAttr()? };
+    let key = unsafe {
+        (
+            (*ref_attr).guid,
+            (*ref_attr).wMajorVerNum,
+            (*ref_attr).wMinorVerNum,
+            (*ref_attr).lcid,
+        )
+    };
+    unsafe { reftypelib.ReleaseTLibAttr(ref_attr) };
+    let mut bstrname = BSTR::default();
+    unsafe { reftypelib.GetDocumentation(-1, Some(&mut bstrname), None, ptr::null_mut(), None)? };
+    Ok((key, bstrname.to_string(), member_name(reftypeinfo, -1)?))
+}
+
+fn member_name(typeinfo: &ITypeInfo, memid: i32) -> Result<String> {
+    let mut bstrname = BSTR::default();
+    unsafe { typeinfo.GetDocumentation(memid, Some(&mut bstrname), None, ptr::null_mut(), None)? };
+    Ok(bstrname.to_string())
+}
+
+/// Collect every `HREFTYPE` used by `typeinfo`: aliased types, implemented
+/// types, method signatures and fields.
+fn type_references(typeinfo: &ITypeInfo) -> Result<Vec<PendingRef>> {
+    let type_name = member_name(typeinfo, -1)?;
+    let type_attr = unsafe { typeinfo.GetTypeAttr()? };
+    let attr = unsafe { &*type_attr };
+    let mut refs = vec![];
+
+    let mut push = |kind, member: Option<&String>, hrefs: Vec<u32>| {
+        for href in hrefs {
+            refs.push(PendingRef {
+                kind,
+                from_type: type_name.clone(),
+                member: member.cloned(),
+                href,
+            });
+        }
+    };
+
+    // `tdescAlias` is only meaningful for aliases.
+    if attr.typekind == TKIND_ALIAS {
+        let mut hrefs = vec![];
+        userdefined_hrefs(&attr.tdescAlias, &mut hrefs);
+        push(TypeRefKind::Alias, None, hrefs);
+    }
+
+    for i in 0..attr.cImplTypes as u32 {
+        if let Ok(href) = unsafe { typeinfo.GetRefTypeOfImplType(i) } {
+            push(TypeRefKind::ImplType, None, vec![href]);
+        }
+    }
+
+    for i in 0..attr.cFuncs as u32 {
+        let Ok(func_desc) = (unsafe { typeinfo.GetFuncDesc(i) }) else {
+            continue;
+        };
+        let desc = unsafe { &*func_desc };
+        let member = member_name(typeinfo, desc.memid).ok();
+
+        let mut hrefs = vec![];
+        userdefined_hrefs(&desc.elemdescFunc.tdesc, &mut hrefs);
+        push(TypeRefKind::Return, member.as_ref(), hrefs);
+
+        let mut hrefs = vec![];
+        for j in 0..desc.cParams as usize {
+            let elem_desc = unsafe { &*desc.lprgelemdescParam.add(j) };
+            userdefined_hrefs(&elem_desc.tdesc, &mut hrefs);
+        }
+        push(TypeRefKind::Param, member.as_ref(), hrefs);
+
+        unsafe { typeinfo.ReleaseFuncDesc(func_desc) };
+    }
+
+    for i in 0..attr.cVars as u32 {
+        let Ok(var_desc) = (unsafe { typeinfo.GetVarDesc(i) }) else {
+            continue;
+        };
+        let desc = unsafe { &*var_desc };
+        let member = member_name(typeinfo, desc.memid).ok();
+
+        let mut hrefs = vec![];
+        userdefined_hrefs(&desc.elemdescVar.tdesc, &mut hrefs);
+        push(TypeRefKind::Field, member.as_ref(), hrefs);
+
+        unsafe { typeinfo.ReleaseVarDesc(var_desc) };
+    }
+
+    unsafe { typeinfo.ReleaseTypeAttr(type_attr) };
+    Ok(refs)
+}
+
+fn userdefined_hrefs(typedesc: &TYPEDESC, hrefs: &mut Vec<u32>) {
+    match typedesc.vt {
+        VT_PTR | VT_SAFEARRAY => {
+            let inner = unsafe { typedesc.Anonymous.lptdesc };
+            if !inner.is_null() {
+                userdefined_hrefs(unsafe { &*inner }, hrefs);
+            }
+        }
+        VT_CARRAY => {
+            let array_desc = unsafe { typedesc.Anonymous.lpadesc };
+            if !array_desc.is_null() {
+                userdefined_hrefs(unsafe { &(*array_desc).tdescElem }, hrefs);
+            }
+        }
+        VT_USERDEFINED => hrefs.push(unsafe { typedesc.Anonymous.hreftype }),
+        _ => {}
+    }
+}
@@ -0,0 +1,241 @@
+use std::ffi::OsStr;
+
+use windows::{
+    core::{Interface, BSTR},
+    Win32::{
+        Foundation::{DISP_E_EXCEPTION, S_FALSE},
+        System::{
+            Com::{
+                IServiceProvider, DISPATCH_FLAGS, DISPATCH_METHOD, DISPATCH_PROPERTYGET,
+                DISPATCH_PROPERTYPUT, DISPATCH_PROPERTYPUTREF, DISPPARAMS, EXCEPINFO,
+            },
+            Ole::{
+                fdexEnumAll, fdexNameCaseInsensitive, fdexNameCaseSensitive, fdexNameEnsure,
+                IDispatchEx, DISPID_PROPERTYPUT, DISPID_STARTENUM, DISPID_THIS,
+            },
+            Variant::VARIANT,
+        },
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    error::{Error, ExceptionInfo, OleError, Result},
+    oledata::render_args,
+    olevalue::OleValue,
+    OleData,
+};
+
+/// An object implementing `IDispatchEx`, such as a JScript object or array or
+/// an HTML DOM node.
+///
+/// The members of these objects are not described by type information, so
+/// [`OleData::ole_methods`] cannot list them. `DispatchEx` enumerates them at
+/// run time, adds and deletes expando properties, and looks names up
+/// case-sensitively, as script engines do.
+#[derive(Clone)]
+pub struct DispatchEx {
+    object: OleData,
+    dispatch_ex: IDispatchEx,
+}
+
+/// A member found by [`DispatchEx::members`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicMember {
+    pub dispid: i32,
+    pub name: String,
+}
+
+impl DispatchEx {
+    pub fn new(object: &OleData) -> Result<DispatchEx> {
+        ensure_apartment()?;
+        match object.dispatch.cast::<IDispatchEx>() {
+            Ok(dispatch_ex) => Ok(DispatchEx {
+                object: object.clone(),
+                dispatch_ex,
+            }),
+            Err(error) => Err(OleError::interface(
+                error.code(),
+                "object does not implement IDispatchEx",
+            )
+            .into()),
+        }
+    }
+    /// The object as a plain `IDispatch`.
+    ///
+    pub fn object(&self) -> &OleData {
+        &self.object
+    }
+    /// Every member of the object, including expandos, in the order the
+    /// object enumerates them.
+    ///
+    pub fn members(&self) -> Result<Vec<DynamicMember>> {
+        ensure_apartment()?;
+        let mut members = vec![];
+        let mut dispid = DISPID_STARTENUM;
+        while let Some(next) = self.next_dispid(dispid)? {
+            let name = unsafe { self.dispatch_ex.GetMemberName(next)? };
+            members.push(DynamicMember {
+                dispid: next,
+                name: name.to_string(),
+            });
+            dispid = next;
+        }
+        Ok(members)
+    }
+    // `GetNextDispID` signals the end of the enumeration with `S_FALSE`,
+    // which the generated wrapper reports as success.
+    fn next_dispid(&self, dispid: i32) -> Result<Option<i32>> {
+        let mut next = DISPID_STARTENUM;
+        let hr = unsafe {
+            (self.dispatch_ex.vtable().GetNextDispID)(
+                self.dispatch_ex.as_raw(),
+                fdexEnumAll as u32,
+                dispid,
+                &mut next,
+            )
+        };
+        if hr == S_FALSE {
+            return Ok(None);
+        }
+        hr.ok()?;
+        Ok(Some(next))
+    }
+    /// Look up a member by its exact name.
+    ///
+    pub fn dispid<S: AsRef<OsStr>>(&self, name: S) -> Result<i32> {
+        self.get_dispid(name, fdexNameCaseSensitive as u32)
+    }
+    /// Look up a member ignoring case, like `IDispatch::GetIDsOfNames`.
+    ///
+    pub fn dispid_ignore_case<S: AsRef<OsStr>>(&self, name: S) -> Result<i32> {
+        self.get_dispid(name, fdexNameCaseInsensitive as u32)
+    }
+    /// Look up a member, creating it as an expando property if it does not
+    /// exist yet.
+    ///
+    pub fn ensure<S: AsRef<OsStr>>(&self, name: S) -> Result<i32> {
+        self.get_dispid(name, (fdexNameCaseSensitive | fdexNameEnsure) as u32)
+    }
+    fn get_dispid<S: AsRef<OsStr>>(&self, name: S, flags: u32) -> Result<i32> {
+        ensure_apartment()?;
+        let name = BSTR::from(name.as_ref().to_string_lossy().as_ref());
+        Ok(unsafe { self.dispatch_ex.GetDispID(&name, flags)? })
+    }
+    pub fn member_name(&self, dispid: i32) -> Result<String> {
+        ensure_apartment()?;
+        Ok(unsafe { self.dispatch_ex.GetMemberName(dispid)? }.to_string())
+    }
+    /// Delete a member by its exact name.
+    ///
+    pub fn delete<S: AsRef<OsStr>>(&self, name: S) -> Result<()> {
+        ensure_apartment()?;
+        let name = BSTR::from(name.as_ref().to_string_lossy().as_ref());
+        unsafe {
+            self.dispatch_ex
+                .DeleteMemberByName(&name, fdexNameCaseSensitive as u32)?
+        };
+        Ok(())
+    }
+    pub fn delete_dispid(&self, dispid: i32) -> Result<()> {
+        ensure_apartment()?;
+        unsafe { self.dispatch_ex.DeleteMemberByDispID(dispid)? };
+        Ok(())
+    }
+    /// Get a property by its exact name.
+    ///
+    #[track_caller]
+    pub fn get<S: AsRef<OsStr>>(&self, name: S) -> Result<OleValue> {
+        let result = self.invoke_ex(self.dispid(name)?, vec![], DISPATCH_PROPERTYGET, None)?;
+        OleValue::try_from(result)
+    }
+    /// Set a property, creating it as an expando property if needed.
+    ///
+    pub fn put<S: AsRef<OsStr>>(&self, name: S, value: VARIANT) -> Result<()> {
+        self.invoke_ex(self.ensure(name)?, vec![value], DISPATCH_PROPERTYPUT, None)?;
+        Ok(())
+    }
+    /// Call a method by its exact name, with `this` bound to `this` if given.
+    ///
+    pub fn call<S: AsRef<OsStr>>(
+        &self,
+        name: S,
+        args: Vec<VARIANT>,
+        this: Option<&OleData>,
+    ) -> Result<VARIANT> {
+        self.invoke_ex(self.dispid(name)?, args, DISPATCH_METHOD, this)
+    }
+    /// Invoke a member through `IDispatchEx::InvokeEx` with `args` given in
+    /// declaration order.
+    ///
+    /// `this` is passed as the `DISPID_THIS` named argument, which script
+    /// engines use as the `this` of a function object. For
+    /// `DISPATCH_PROPERTYPUT` and `DISPATCH_PROPERTYPUTREF` the last argument
+    /// is passed as the new value of the property.
+    ///
+    pub fn invoke_ex(
+        &self,
+        dispid: i32,
+        args: Vec<VARIANT>,
+        flags: DISPATCH_FLAGS,
+        this: Option<&OleData>,
+    ) -> Result<VARIANT> {
+        ensure_apartment()?;
+        let mut positional: Vec<VARIANT> = args.into_iter().rev().collect();
+        let mut named_ids = vec![];
+        let mut args = vec![];
+        if flags.0 & (DISPATCH_PROPERTYPUT.0 | DISPATCH_PROPERTYPUTREF.0) != 0
+            && !positional.is_empty()
+        {
+            named_ids.push(DISPID_PROPERTYPUT);
+            args.push(positional.remove(0));
+        }
+        if let Some(this) = this {
+            named_ids.push(DISPID_THIS);
+            args.push(VARIANT::from(this.dispatch.clone()));
+        }
+        args.extend(positional);
+
+        let mut dp = DISPPARAMS {
+            rgvarg: args.as_mut_ptr(),
+            rgdispidNamedArgs: named_ids.as_mut_ptr(),
+            cArgs: args.len() as u32,
+            cNamedArgs: named_ids.len() as u32,
+        };
+        self.object
+            .retrying(|| self.invoke_once(dispid, &mut dp, flags))
+    }
+    fn invoke_once(
+        &self,
+        dispid: i32,
+        dp: &mut DISPPARAMS,
+        flags: DISPATCH_FLAGS,
+    ) -> Result<VARIANT> {
+        let mut excep = EXCEPINFO::default();
+        let mut result = VARIANT::default();
+
+        let res = unsafe {
+            self.dispatch_ex.InvokeEx(
+                dispid,
+                0x0800, /*LOCALE_SYSTEM_DEFAULT*/
+                flags.0,
+                dp,
+                Some(&mut result),
+                Some(&mut excep),
+                None::<&IServiceProvider>,
+            )
+        };
+
+        match res {
+            Ok(()) => Ok(result),
+            Err(e) if e.code() == DISP_E_EXCEPTION => {
+                let info =
+                    ExceptionInfo::capture(excep, dispid, self.member_name(dispid).ok(), unsafe {
+                        render_args(dp)
+                    });
+                Err(Error::Exception(Box::new(info)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
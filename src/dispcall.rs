@@ -0,0 +1,340 @@
+use std::{ffi::c_void, ptr, slice};
+
+use windows::{
+    core::{IUnknown, Interface, GUID, HRESULT},
+    Win32::{
+        Foundation::DISP_E_PARAMNOTFOUND,
+        System::{
+            Com::{
+                ITypeInfo, ELEMDESC, FUNC_DISPATCH, FUNC_PUREVIRTUAL, FUNC_VIRTUAL, TKIND_ALIAS,
+                TKIND_DISPATCH, TKIND_ENUM, TKIND_INTERFACE, TYPEDESC,
+            },
+            Ole::{DispCallFunc, PARAMFLAGS, PARAMFLAG_FHASDEFAULT, PARAMFLAG_FRETVAL},
+            Variant::{
+                VariantChangeType, VARENUM, VARIANT, VAR_CHANGE_FLAGS, VT_ARRAY, VT_BYREF,
+                VT_DISPATCH, VT_ERROR, VT_HRESULT, VT_I4, VT_PTR, VT_SAFEARRAY, VT_UNKNOWN,
+                VT_USERDEFINED, VT_VARIANT, VT_VOID,
+            },
+        },
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    error::{ComArgumentErrorType, Error, Result},
+    olemethoddata::ole_declared_methods,
+    util::ole::TypeRef,
+    OleData, OleMethodData, OleTypeData,
+};
+
+/// Call `method` through the vtable of `object` instead of `IDispatch::Invoke`.
+///
+/// Methods of a dual interface's dispinterface are mapped to the same member
+/// of the interface half, which is what actually has a vtable slot.
+pub(crate) fn invoke_vtbl(
+    object: &OleData,
+    method: &OleMethodData,
+    args: Vec<VARIANT>,
+) -> Result<VARIANT> {
+    ensure_apartment()?;
+    let interface_method;
+    let method = match method.funckind() {
+        FUNC_VIRTUAL | FUNC_PUREVIRTUAL => method,
+        FUNC_DISPATCH => {
+            interface_method = interface_function(method)?;
+            &interface_method
+        }
+        _ => {
+            return Err(Error::Custom(format!(
+                "`{}` is not reachable through a vtable",
+                method.name()
+            )))
+        }
+    };
+    let iid = OleTypeData::try_from(method.typeinfo().clone())?.guid();
+    let interface = query(object, &iid)?;
+    unsafe {
+        dispcall(
+            method,
+            Some(interface.as_raw().cast_const()),
+            method.offset_vtbl()? as usize,
+            args,
+        )
+    }
+}
+
+fn query(object: &OleData, iid: &GUID) -> Result<IUnknown> {
+    let mut interface = ptr::null_mut();
+    unsafe { object.dispatch.query(iid, &mut interface).ok()? };
+    // `IUnknown` only releases the reference when dropped; it is never
+    // called through as `IUnknown` itself.
+    Ok(unsafe { IUnknown::from_raw(interface) })
+}
+
+/// The member of the dual interface behind a dispinterface member, matched
+/// by DISPID and invoke kind.
+fn interface_function(method: &OleMethodData) -> Result<OleMethodData> {
+    let typeinfo = method.typeinfo();
+    let href = unsafe { typeinfo.GetRefTypeOfImplType((-1i32) as u32) }.map_err(|_| {
+        Error::Custom(format!(
+            "`{}` belongs to a dispinterface that is not dual and has no vtable",
+            method.name()
+        ))
+    })?;
+    let interface = OleTypeData::try_from(unsafe { typeinfo.GetRefTypeInfo(href)? })?;
+    for declaring in interface.inheritance_chain()? {
+        for function in ole_declared_methods(declaring.typeinfo())? {
+            if function.dispid() == method.dispid() && function.invkind() == method.invkind() {
+                return Ok(function);
+            }
+        }
+    }
+    Err(Error::Custom(format!(
+        "`{}` has no entry in the vtable of `{}`",
+        method.name(),
+        interface.name()
+    )))
+}
+
+/// Call the function described by `method` with `DispCallFunc`: the entry at
+/// byte `offset` of the vtable of `instance`, or the function at address
+/// `offset` when there is no instance.
+///
+/// `args` are in declaration order and are coerced to the parameter types of
+/// the `FUNCDESC`. The `[retval]` parameter is not passed in `args`; it is
+/// returned instead, and a failed `HRESULT` return becomes an error.
+///
+/// A `VT_BYREF` argument for an `[out]` or `[in, out]` parameter is passed
+/// through as is, so the callee writes to the memory it points to. It must be
+/// a reference to the parameter's type, or a `VT_BYREF | VT_VARIANT` whose
+/// VARIANT holds a value of that type; other references are a type mismatch.
+/// A value that is not a reference is copied into a temporary for the callee,
+/// and what the callee writes there is lost.
+///
+/// # Safety
+///
+/// `instance` and `offset` must point to a function with the signature that
+/// `method` describes.
+pub(crate) unsafe fn dispcall(
+    method: &OleMethodData,
+    instance: Option<*const c_void>,
+    offset: usize,
+    args: Vec<VARIANT>,
+) -> Result<VARIANT> {
+    let desc = method.desc();
+    let params: &[ELEMDESC] = if desc.cParams > 0 {
+        slice::from_raw_parts(desc.lprgelemdescParam, desc.cParams as usize)
+    } else {
+        &[]
+    };
+    let typeinfo = method.typeinfo();
+    let mut args = args.into_iter();
+    let mut values = Vec::with_capacity(params.len());
+    let mut types = Vec::with_capacity(params.len());
+    // Pointed to by `VT_BYREF` values, so boxed to keep their address.
+    let mut storage: Vec<Box<VARIANT>> = vec![];
+    let mut retval = None;
+
+    for (i, param) in params.iter().enumerate() {
+        let flags = param.Anonymous.paramdesc.wParamFlags;
+        let tdesc = &param.tdesc;
+        if tdesc.vt == VT_PTR && !is_interface(typeinfo, &*tdesc.Anonymous.lptdesc)? {
+            let vt = param_type(typeinfo, &*tdesc.Anonymous.lptdesc)?.0;
+            if flags & PARAMFLAG_FRETVAL != PARAMFLAGS(0) {
+                retval = Some(storage.len());
+                storage.push(Box::new(empty_of(vt)));
+            } else {
+                match args.next() {
+                    Some(arg) if arg.Anonymous.Anonymous.vt.0 & VT_BYREF.0 != 0 => {
+                        let value = byref_arg(arg, vt).ok_or_else(|| type_mismatch(i))?;
+                        types.push(VARENUM(VT_BYREF.0 | vt.0));
+                        values.push(value);
+                        continue;
+                    }
+                    Some(arg) => storage.push(Box::new(coerce(&arg, vt, i)?)),
+                    None => storage.push(Box::new(empty_of(vt))),
+                }
+            }
+            let target = storage.last_mut().unwrap();
+            types.push(VARENUM(VT_BYREF.0 | vt.0));
+            values.push(byref_to(target, vt));
+        } else {
+            let (vt, iid) = param_type(typeinfo, tdesc)?;
+            let value = match args.next() {
+                Some(arg) => match iid {
+                    Some(iid) => interface_arg(&arg, vt, &iid, i)?,
+                    None => coerce(&arg, vt, i)?,
+                },
+                None => missing_arg(param, vt, i)?,
+            };
+            types.push(vt);
+            values.push(value);
+        }
+    }
+    let pointers: Vec<*const VARIANT> = values.iter().map(|value| value as *const _).collect();
+    let types: Vec<u16> = types.iter().map(|vt| vt.0).collect();
+    let return_type = match desc.elemdescFunc.tdesc.vt {
+        VT_HRESULT | VT_VOID => desc.elemdescFunc.tdesc.vt,
+        _ => param_type(typeinfo, &desc.elemdescFunc.tdesc)?.0,
+    };
+
+    let mut result = DispCallFunc(
+        instance,
+        offset,
+        method.callconv(),
+        return_type,
+        types.len() as u32,
+        types.as_ptr(),
+        pointers.as_ptr(),
+    )?;
+
+    if return_type == VT_HRESULT {
+        HRESULT(result.Anonymous.Anonymous.Anonymous.scode).ok()?;
+        result = VARIANT::default();
+    }
+    match retval {
+        Some(index) => Ok(*storage.swap_remove(index)),
+        None => Ok(result),
+    }
+}
+
+/// The VARTYPE a value of type `tdesc` is passed as, with the IID when it is
+/// an interface pointer.
+unsafe fn param_type(typeinfo: &ITypeInfo, tdesc: &TYPEDESC) -> Result<(VARENUM, Option<GUID>)> {
+    match tdesc.vt {
+        VT_USERDEFINED => {
+            let ref_type =
+                OleTypeData::try_from(typeinfo.GetRefTypeInfo(tdesc.Anonymous.hreftype)?)?;
+            match ref_type.typekind() {
+                TKIND_ENUM => Ok((VT_I4, None)),
+                TKIND_ALIAS => param_type(ref_type.typeinfo(), &ref_type.attribs().tdescAlias),
+                TKIND_INTERFACE => Ok((VT_UNKNOWN, Some(ref_type.guid()))),
+                TKIND_DISPATCH => Ok((VT_DISPATCH, Some(ref_type.guid()))),
+                _ => Err(Error::Custom(format!(
+                    "`{}` cannot be passed in a vtable call",
+                    ref_type.name()
+                ))),
+            }
+        }
+        VT_SAFEARRAY => {
+            let element = param_type(typeinfo, &*tdesc.Anonymous.lptdesc)?.0;
+            Ok((VARENUM(VT_ARRAY.0 | element.0), None))
+        }
+        VT_PTR if is_interface(typeinfo, &*tdesc.Anonymous.lptdesc)? => {
+            param_type(typeinfo, &*tdesc.Anonymous.lptdesc)
+        }
+        VT_PTR | VT_VOID | VT_HRESULT => Err(Error::Custom(format!(
+            "VARIANT type {} cannot be passed in a vtable call",
+            tdesc.vt.0
+        ))),
+        vt => Ok((vt, None)),
+    }
+}
+
+unsafe fn is_interface(typeinfo: &ITypeInfo, tdesc: &TYPEDESC) -> Result<bool> {
+    if tdesc.vt != VT_USERDEFINED {
+        return Ok(false);
+    }
+    let ref_type = OleTypeData::try_from(typeinfo.GetRefTypeInfo(tdesc.Anonymous.hreftype)?)?;
+    Ok(matches!(
+        ref_type.typekind(),
+        TKIND_INTERFACE | TKIND_DISPATCH
+    ))
+}
+
+fn coerce(arg: &VARIANT, vt: VARENUM, index: usize) -> Result<VARIANT> {
+    match vt {
+        VT_VARIANT => Ok(arg.clone()),
+        vt if unsafe { arg.Anonymous.Anonymous.vt } == vt => Ok(arg.clone()),
+        _ => {
+            let mut value = VARIANT::default();
+            match unsafe { VariantChangeType(&mut value, arg, VAR_CHANGE_FLAGS(0), vt) } {
+                Ok(()) => Ok(value),
+                Err(_) => Err(type_mismatch(index)),
+            }
+        }
+    }
+}
+
+/// Query the interface held by `arg` for `iid`, so that the callee gets the
+/// vtable it expects.
+fn interface_arg(arg: &VARIANT, vt: VARENUM, iid: &GUID, index: usize) -> Result<VARIANT> {
+    let unknown = coerce(arg, VT_UNKNOWN, index)?;
+    let Some(unknown) = (unsafe { &*unknown.Anonymous.Anonymous.Anonymous.punkVal }) else {
+        return Ok(empty_of(vt));
+    };
+    let mut interface = ptr::null_mut();
+    if unsafe { unknown.query(iid, &mut interface) }.is_err() {
+        return Err(type_mismatch(index));
+    }
+    Ok(VARIANT::from(unsafe { IUnknown::from_raw(interface) }))
+}
+
+fn missing_arg(param: &ELEMDESC, vt: VARENUM, index: usize) -> Result<VARIANT> {
+    let paramdesc = unsafe { &param.Anonymous.paramdesc };
+    if paramdesc.wParamFlags & PARAMFLAG_FHASDEFAULT != PARAMFLAGS(0) {
+        let default = unsafe { &(*paramdesc.pparamdescex).varDefaultValue };
+        return coerce(default, vt, index);
+    }
+    if vt == VT_VARIANT {
+        // What VB passes for an omitted optional argument.
+        let mut missing = VARIANT::default();
+        unsafe {
+            (*missing.Anonymous.Anonymous).vt = VT_ERROR;
+            (*missing.Anonymous.Anonymous).Anonymous.scode = DISP_E_PARAMNOTFOUND.0;
+        }
+        return Ok(missing);
+    }
+    Err(Error::IDispatchArgument {
+        error_type: ComArgumentErrorType::ParameterNotFound,
+        arg_err: index as u32,
+    })
+}
+
+fn type_mismatch(index: usize) -> Error {
+    Error::IDispatchArgument {
+        error_type: ComArgumentErrorType::TypeMismatch,
+        arg_err: index as u32,
+    }
+}
+
+/// `arg` as a reference to a value of type `vt`, or `None` if it refers to
+/// anything else, which the callee would write through as the wrong type.
+unsafe fn byref_arg(arg: VARIANT, vt: VARENUM) -> Option<VARIANT> {
+    let arg_vt = arg.Anonymous.Anonymous.vt;
+    if arg_vt.0 == VT_BYREF.0 | vt.0 {
+        return Some(arg);
+    }
+    if arg_vt.0 == VT_BYREF.0 | VT_VARIANT.0 {
+        let inner = arg.Anonymous.Anonymous.Anonymous.pvarVal.as_mut()?;
+        if inner.Anonymous.Anonymous.vt == vt {
+            return Some(byref_to(inner, vt));
+        }
+    }
+    None
+}
+
+/// An empty value of type `vt`, ready to receive an `[out]` argument.
+fn empty_of(vt: VARENUM) -> VARIANT {
+    let mut value = VARIANT::default();
+    if vt != VT_VARIANT {
+        unsafe { (*value.Anonymous.Anonymous).vt = vt };
+    }
+    value
+}
+
+/// A `VT_BYREF` VARIANT pointing at the data of `target`, or at `target`
+/// itself for `VT_VARIANT`.
+fn byref_to(target: &mut VARIANT, vt: VARENUM) -> VARIANT {
+    let mut value = VARIANT::default();
+    unsafe {
+        let pointer: *mut c_void = if vt == VT_VARIANT {
+            target as *mut VARIANT as *mut c_void
+        } else {
+            &mut (*target.Anonymous.Anonymous).Anonymous as *mut _ as *mut c_void
+        };
+        (*value.Anonymous.Anonymous).vt = VARENUM(VT_BYREF.0 | vt.0);
+        (*value.Anonymous.Anonymous).Anonymous.byref = pointer;
+    }
+    value
+}
@@ -0,0 +1,118 @@
+use std::{ffi::CString, fmt};
+
+use windows::{
+    core::{BSTR, PCSTR, PCWSTR},
+    Win32::{
+        Foundation::{FreeLibrary, HMODULE},
+        System::{
+            Com::FUNC_STATIC,
+            LibraryLoader::{GetProcAddress, LoadLibraryW},
+            Variant::VARIANT,
+        },
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    dispcall::dispcall,
+    error::{Error, Result},
+    util::{conv::ToWide, ole::TypeRef},
+    OleMethodData,
+};
+
+/// Where a function declared in a type library `module` block lives, from its
+/// `[dllname]` and `[entry]` attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DllEntry {
+    pub dll_name: String,
+    pub entry_point: EntryPoint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryPoint {
+    Name(String),
+    Ordinal(u16),
+}
+
+impl fmt::Display for DllEntry {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.entry_point {
+            EntryPoint::Name(name) => write!(fmt, "{}!{name}", self.dll_name),
+            EntryPoint::Ordinal(ordinal) => write!(fmt, "{}!#{ordinal}", self.dll_name),
+        }
+    }
+}
+
+/// The DLL entry of `method`, or `None` unless it is a static function of a
+/// `TKIND_MODULE` type.
+pub(crate) fn dll_entry(method: &OleMethodData) -> Result<Option<DllEntry>> {
+    if method.funckind() != FUNC_STATIC {
+        return Ok(None);
+    }
+    let mut dll_name = BSTR::default();
+    let mut name = BSTR::default();
+    let mut ordinal = 0;
+    unsafe {
+        method.typeinfo().GetDllEntry(
+            method.dispid(),
+            method.invkind(),
+            Some(&mut dll_name),
+            Some(&mut name),
+            &mut ordinal,
+        )?
+    };
+    let entry_point = if name.is_empty() {
+        EntryPoint::Ordinal(ordinal)
+    } else {
+        EntryPoint::Name(name.to_string())
+    };
+    Ok(Some(DllEntry {
+        dll_name: dll_name.to_string(),
+        entry_point,
+    }))
+}
+
+/// Load the DLL of a module function and call it with `args` in declaration
+/// order, marshalled as described by its `FUNCDESC`.
+pub(crate) fn invoke_dll_entry(method: &OleMethodData, args: Vec<VARIANT>) -> Result<VARIANT> {
+    ensure_apartment()?;
+    let Some(entry) = dll_entry(method)? else {
+        return Err(Error::Custom(format!(
+            "`{}` is not a function of a type library module",
+            method.name()
+        )));
+    };
+    let library = Library::load(&entry.dll_name)?;
+    let function = match &entry.entry_point {
+        EntryPoint::Name(name) => {
+            let name = CString::new(name.as_str())
+                .map_err(|_| Error::Custom(format!("invalid entry point name `{name}`")))?;
+            unsafe { GetProcAddress(library.0, PCSTR::from_raw(name.as_ptr() as *const u8)) }
+        }
+        // MAKEINTRESOURCEA
+        EntryPoint::Ordinal(ordinal) => unsafe {
+            GetProcAddress(library.0, PCSTR::from_raw(*ordinal as usize as *const u8))
+        },
+    };
+    let Some(function) = function else {
+        return Err(Error::Custom(format!("entry point {entry} not found")));
+    };
+    unsafe { dispcall(method, None, function as usize, args) }
+}
+
+/// A loaded DLL, freed when dropped.
+struct Library(HMODULE);
+
+impl Library {
+    fn load(name: &str) -> Result<Library> {
+        let name = name.to_wide_null();
+        let module = unsafe { LoadLibraryW(PCWSTR::from_raw(name.as_ptr()))? };
+        Ok(Library(module))
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        let _ = unsafe { FreeLibrary(self.0) };
+    }
+}
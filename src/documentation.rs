@@ -0,0 +1,150 @@
+use windows::{
+    core::BSTR,
+    Win32::{
+        Globalization::GetUserDefaultLCID,
+        System::Com::{ITypeInfo, ITypeLib},
+    },
+};
+
+use crate::{
+    error::Result,
+    util::ole::{typeinfo2, typelib2},
+};
+
+/// Documentation attached to a type library, type or member, as returned by
+/// `GetDocumentation`.
+///
+/// `help_string_dll` is only filled in when the owning type info or type library
+/// implements `ITypeInfo2`/`ITypeLib2`; it is empty otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Documentation {
+    pub name: String,
+    pub doc_string: String,
+    pub help_context: u32,
+    pub help_file: String,
+    pub help_string_dll: String,
+}
+
+/// Localized help string information, as returned by `GetDocumentation2`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizedDocumentation {
+    pub help_string: String,
+    pub help_string_context: u32,
+    pub help_string_dll: String,
+}
+
+/// Implemented by every metadata object that carries documentation.
+pub trait Documented {
+    /// Get the name, doc string, help context and help files in one call.
+    ///
+    fn documentation(&self) -> Result<Documentation>;
+    /// Get the help string localized for `lcid` through `GetDocumentation2`.
+    ///
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation>;
+}
+
+pub(crate) fn typeinfo_documentation(typeinfo: &ITypeInfo, memid: i32) -> Result<Documentation> {
+    let mut name = BSTR::default();
+    let mut doc_string = BSTR::default();
+    let mut help_context = 0;
+    let mut help_file = BSTR::default();
+    unsafe {
+        typeinfo.GetDocumentation(
+            memid,
+            Some(&mut name),
+            Some(&mut doc_string),
+            &mut help_context,
+            Some(&mut help_file),
+        )?
+    };
+    let help_string_dll =
+        typeinfo_localized_documentation(typeinfo, memid, unsafe { GetUserDefaultLCID() })
+            .map(|localized| localized.help_string_dll)
+            .unwrap_or_default();
+
+    Ok(Documentation {
+        name: String::try_from(name)?,
+        doc_string: String::try_from(doc_string)?,
+        help_context,
+        help_file: String::try_from(help_file)?,
+        help_string_dll,
+    })
+}
+
+pub(crate) fn typeinfo_localized_documentation(
+    typeinfo: &ITypeInfo,
+    memid: i32,
+    lcid: u32,
+) -> Result<LocalizedDocumentation> {
+    let typeinfo2 = typeinfo2(typeinfo)?;
+    let mut help_string = BSTR::default();
+    let mut help_string_context = 0;
+    let mut help_string_dll = BSTR::default();
+    unsafe {
+        typeinfo2.GetDocumentation2(
+            memid,
+            lcid,
+            Some(&mut help_string),
+            &mut help_string_context,
+            Some(&mut help_string_dll),
+        )?
+    };
+    Ok(LocalizedDocumentation {
+        help_string: String::try_from(help_string)?,
+        help_string_context,
+        help_string_dll: String::try_from(help_string_dll)?,
+    })
+}
+
+pub(crate) fn typelib_documentation(typelib: &ITypeLib, index: i32) -> Result<Documentation> {
+    let mut name = BSTR::default();
+    let mut doc_string = BSTR::default();
+    let mut help_context = 0;
+    let mut help_file = BSTR::default();
+    unsafe {
+        typelib.GetDocumentation(
+            index,
+            Some(&mut name),
+            Some(&mut doc_string),
+            &mut help_context,
+            Some(&mut help_file),
+        )?
+    };
+    let help_string_dll =
+        typelib_localized_documentation(typelib, index, unsafe { GetUserDefaultLCID() })
+            .map(|localized| localized.help_string_dll)
+            .unwrap_or_default();
+
+    Ok(Documentation {
+        name: String::try_from(name)?,
+        doc_string: String::try_from(doc_string)?,
+        help_context,
+        help_file: String::try_from(help_file)?,
+        help_string_dll,
+    })
+}
+
+pub(crate) fn typelib_localized_documentation(
+    typelib: &ITypeLib,
+    index: i32,
+    lcid: u32,
+) -> Result<LocalizedDocumentation> {
+    let typelib2 = typelib2(typelib)?;
+    let mut help_string = BSTR::default();
+    let mut help_string_context = 0;
+    let mut help_string_dll = BSTR::default();
+    unsafe {
+        typelib2.GetDocumentation2(
+            index,
+            lcid,
+            Some(&mut help_string),
+            &mut help_string_context,
+            Some(&mut help_string_dll),
+        )?
+    };
+    Ok(LocalizedDocumentation {
+        help_string: String::try_from(help_string)?,
+        help_string_context,
+        help_string_dll: String::try_from(help_string_dll)?,
+    })
+}
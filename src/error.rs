@@ -11,6 +11,8 @@ use windows::{
     Win32::{Foundation::WIN32_ERROR, System::Com::EXCEPINFO},
 };
 
+use crate::hresult::HResult;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug)]
@@ -25,11 +27,57 @@ pub enum Error {
     Generic(&'static str),
     Custom(String),
     Ole(OleError),
-    Exception(EXCEPINFO),
+    Exception(Box<ExceptionInfo>),
     IDispatchArgument {
         error_type: ComArgumentErrorType,
         arg_err: u32,
     },
+    /// A segment of an [`OleData::navigate`](crate::OleData::navigate) path failed.
+    Navigation {
+        path: String,
+        segment: String,
+        /// Character offset of the segment within the path.
+        position: usize,
+        source: Box<Error>,
+    },
+    /// A moniker display name could not be parsed.
+    MonikerSyntax {
+        display_name: String,
+        /// Number of UTF-16 units consumed before parsing failed.
+        position: usize,
+        source: windows::core::Error,
+    },
+    /// A property path could not be parsed.
+    PathSyntax {
+        path: String,
+        position: usize,
+        message: &'static str,
+    },
+}
+
+impl Error {
+    /// The HRESULT behind this error, if it came from a COM call.
+    ///
+    /// For `Exception` this is the `scode` reported by the server, or
+    /// `DISP_E_EXCEPTION` when it did not set one.
+    ///
+    pub fn hresult(&self) -> Option<HResult> {
+        match self {
+            Error::Windows(err) => Some(err.code().into()),
+            Error::Ole(err) => Some(err.hresult()),
+            Error::Io(err) => err
+                .raw_os_error()
+                .map(|code| HResult::from_win32(code as u32)),
+            Error::Exception(info) => Some(info.hresult()),
+            Error::IDispatchArgument { error_type, .. } => Some(match error_type {
+                ComArgumentErrorType::TypeMismatch => HResult::DISP_E_TYPEMISMATCH,
+                ComArgumentErrorType::ParameterNotFound => HResult::DISP_E_PARAMNOTFOUND,
+            }),
+            Error::Navigation { source, .. } => source.hresult(),
+            Error::MonikerSyntax { source, .. } => Some(source.code().into()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -72,6 +120,15 @@ impl OleError {
     pub fn interface<S: AsRef<str>, H: Into<HRESULT>>(hresult: H, context_message: S) -> OleError {
         OleError::new(OleErrorType::QueryInterface, hresult, context_message)
     }
+    pub fn hresult(&self) -> HResult {
+        self.hresult.into()
+    }
+    pub fn error_type(&self) -> &OleErrorType {
+        &self.error_type
+    }
+    pub fn context_message(&self) -> &str {
+        &self.context_message
+    }
 }
 
 impl fmt::Display for OleError {
@@ -115,7 +172,18 @@ impl fmt::Display for ComArgumentErrorType {
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(self)
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Windows(err) => Some(err),
+            Error::Utf8(err) => Some(err),
+            Error::Utf16(err) => Some(err),
+            Error::ParseFloat(err) => Some(err),
+            Error::FromInt(err) => Some(err),
+            Error::IntoString(err) => Some(err),
+            Error::Navigation { source, .. } => Some(&**source),
+            Error::MonikerSyntax { source, .. } => Some(source),
+            _ => None,
+        }
     }
 }
 
@@ -181,7 +249,7 @@ impl fmt::Display for Error {
             Generic(ref err) => err.fmt(fmt),
             Custom(ref err) => err.fmt(fmt),
             Ole(ref err) => err.fmt(fmt),
-            Exception(excepinfo) => writeln!(fmt, "{}", ole_excepinfo2msg(excepinfo)),
+            Exception(info) => info.fmt(fmt),
             IDispatchArgument {
                 error_type,
                 arg_err,
@@ -189,48 +257,109 @@ impl fmt::Display for Error {
                 fmt,
                 "COM argument error {error_type} for argument {arg_err}"
             ),
+            Navigation {
+                path,
+                segment,
+                position,
+                source,
+            } => write!(
+                fmt,
+                "failed to evaluate `{segment}` at position {position} of `{path}`: {source}"
+            ),
+            MonikerSyntax {
+                display_name,
+                position,
+                source,
+            } => write!(
+                fmt,
+                "cannot parse moniker `{display_name}` at position {position}: {source}"
+            ),
+            PathSyntax {
+                path,
+                position,
+                message,
+            } => write!(fmt, "{message} at position {position} of `{path}`"),
         }
     }
 }
 
-fn ole_excepinfo2msg(excepinfo: &EXCEPINFO) -> String {
-    let mut excepinfo = excepinfo.clone();
-    if let Some(func) = excepinfo.pfnDeferredFillIn {
-        let _ = unsafe { func(&mut excepinfo) };
-    }
-
-    let s = &excepinfo.bstrSource;
-    let source = if !s.is_empty() {
-        s.to_string()
-    } else {
-        String::new()
-    };
-    let d = &excepinfo.bstrDescription;
-    let description = if !d.is_empty() {
-        d.to_string()
-    } else {
-        String::new()
-    };
-    let mut msg = if excepinfo.wCode == 0 {
-        format!("\n    OLE error code: {} in ", excepinfo.scode)
-    } else {
-        format!("\n    OLE error code: {} in ", excepinfo.wCode)
-    };
+/// An exception raised by an automation server through `IDispatch::Invoke`,
+/// copied out of its `EXCEPINFO` when the call failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExceptionInfo {
+    pub source: String,
+    pub description: String,
+    pub help_file: String,
+    pub help_context: u32,
+    /// Server-defined error code; `0` when `scode` is used instead.
+    pub code: u16,
+    pub scode: i32,
+    /// Name of the invoked member, when it could be determined.
+    pub member: Option<String>,
+    pub dispid: i32,
+    /// The arguments passed to the call, rendered in declaration order.
+    pub args: Vec<String>,
+}
 
-    if !source.is_empty() {
-        msg.push_str(&source);
-    } else {
-        msg.push_str("<Unknown>");
+impl ExceptionInfo {
+    /// Copy `excepinfo`, running its deferred fill-in first, and free its strings.
+    pub(crate) fn capture(
+        mut excepinfo: EXCEPINFO,
+        dispid: i32,
+        member: Option<String>,
+        args: Vec<String>,
+    ) -> ExceptionInfo {
+        if let Some(func) = excepinfo.pfnDeferredFillIn.take() {
+            let _ = unsafe { func(&mut excepinfo) };
+        }
+        ExceptionInfo {
+            source: excepinfo.bstrSource.to_string(),
+            description: excepinfo.bstrDescription.to_string(),
+            help_file: excepinfo.bstrHelpFile.to_string(),
+            help_context: excepinfo.dwHelpContext,
+            code: excepinfo.wCode,
+            scode: excepinfo.scode,
+            member,
+            dispid,
+            args,
+        }
     }
-    msg.push_str("\n      ");
-    if !description.is_empty() {
-        msg.push_str(&description);
-    } else {
-        msg.push_str("<No Description>");
+    /// `scode`, or `DISP_E_EXCEPTION` when the server did not set one.
+    ///
+    pub fn hresult(&self) -> HResult {
+        if self.scode != 0 {
+            HResult(self.scode)
+        } else {
+            HResult::DISP_E_EXCEPTION
+        }
     }
+}
 
-    let _ = excepinfo.bstrSource;
-    let _ = excepinfo.bstrDescription;
-
-    msg
+impl fmt::Display for ExceptionInfo {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.code == 0 {
+            write!(fmt, "OLE error code: {}", self.hresult())?;
+        } else {
+            write!(fmt, "OLE error code: {}", self.code)?;
+        }
+        let source = if self.source.is_empty() {
+            "<Unknown>"
+        } else {
+            &self.source
+        };
+        write!(fmt, " in {source}")?;
+        match &self.member {
+            Some(member) => write!(fmt, " calling `{member}`")?,
+            None => write!(fmt, " calling DISPID {}", self.dispid)?,
+        }
+        if !self.args.is_empty() {
+            write!(fmt, "({})", self.args.join(", "))?;
+        }
+        let description = if self.description.is_empty() {
+            "<No Description>"
+        } else {
+            &self.description
+        };
+        write!(fmt, "\n    {description}")
+    }
 }
@@ -0,0 +1,309 @@
+use std::fmt;
+
+use windows::core::HRESULT;
+
+/// A decoded HRESULT: severity bit, facility and code.
+///
+/// Well-known COM, OLE, dispatch and RPC codes are available as associated
+/// constants and carry a symbolic name and description, e.g.
+/// `HResult::RPC_E_CALL_REJECTED.name() == Some("RPC_E_CALL_REJECTED")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HResult(pub i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Success,
+    Error,
+}
+
+impl HResult {
+    pub const fn from_u32(value: u32) -> HResult {
+        HResult(value as i32)
+    }
+    /// The HRESULT for a Win32 error code, like `HRESULT_FROM_WIN32`.
+    ///
+    pub const fn from_win32(code: u32) -> HResult {
+        if code as i32 <= 0 {
+            HResult(code as i32)
+        } else {
+            HResult::from_u32((code & 0xFFFF) | (FACILITY_WIN32 << 16) | 0x8000_0000)
+        }
+    }
+    pub const fn as_u32(self) -> u32 {
+        self.0 as u32
+    }
+    pub const fn severity(self) -> Severity {
+        if self.0 < 0 {
+            Severity::Error
+        } else {
+            Severity::Success
+        }
+    }
+    pub const fn is_error(self) -> bool {
+        self.0 < 0
+    }
+    pub const fn facility(self) -> u16 {
+        ((self.as_u32() >> 16) & 0x1FFF) as u16
+    }
+    pub fn facility_name(self) -> Option<&'static str> {
+        FACILITIES
+            .iter()
+            .find(|(facility, _)| *facility == self.facility())
+            .map(|(_, name)| *name)
+    }
+    pub const fn code(self) -> u16 {
+        (self.as_u32() & 0xFFFF) as u16
+    }
+    /// The Win32 error code wrapped by a `FACILITY_WIN32` HRESULT.
+    ///
+    pub const fn win32_code(self) -> Option<u32> {
+        if self.facility() as u32 == FACILITY_WIN32 {
+            Some(self.code() as u32)
+        } else {
+            None
+        }
+    }
+    /// Symbolic name from the built-in message table.
+    ///
+    pub fn name(self) -> Option<&'static str> {
+        self.entry().map(|entry| entry.1)
+    }
+    /// Description from the built-in message table.
+    ///
+    pub fn description(self) -> Option<&'static str> {
+        self.entry().map(|entry| entry.2)
+    }
+    /// The system's message for the code, as returned by `FormatMessage`.
+    ///
+    pub fn system_message(self) -> String {
+        HRESULT(self.0).message()
+    }
+    /// The call may succeed if retried: the server was busy, rejected the
+    /// call or asked to be called back later.
+    ///
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            HResult::RPC_E_CALL_REJECTED
+                | HResult::RPC_E_SERVERCALL_RETRYLATER
+                | HResult::RPC_E_SERVERCALL_REJECTED
+        )
+    }
+    fn entry(self) -> Option<&'static (u32, &'static str, &'static str)> {
+        MESSAGES
+            .iter()
+            .find(|(value, _, _)| *value == self.as_u32())
+    }
+}
+
+impl From<HRESULT> for HResult {
+    fn from(hresult: HRESULT) -> Self {
+        HResult(hresult.0)
+    }
+}
+
+impl From<HResult> for HRESULT {
+    fn from(hresult: HResult) -> Self {
+        HRESULT(hresult.0)
+    }
+}
+
+impl fmt::Display for HResult {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{:#010X}", self.as_u32())?;
+        match self.entry() {
+            Some((_, name, description)) => write!(fmt, " ({name}: {description})"),
+            None => Ok(()),
+        }
+    }
+}
+
+const FACILITY_WIN32: u32 = 7;
+
+const FACILITIES: &[(u16, &str)] = &[
+    (0, "FACILITY_NULL"),
+    (1, "FACILITY_RPC"),
+    (2, "FACILITY_DISPATCH"),
+    (3, "FACILITY_STORAGE"),
+    (4, "FACILITY_ITF"),
+    (7, "FACILITY_WIN32"),
+    (8, "FACILITY_WINDOWS"),
+    (9, "FACILITY_SECURITY"),
+    (10, "FACILITY_CONTROL"),
+    (11, "FACILITY_CERT"),
+    (12, "FACILITY_INTERNET"),
+    (17, "FACILITY_SSPI"),
+];
+
+macro_rules! message_table {
+    ($($name:ident = $value:literal, $description:literal;)*) => {
+        impl HResult {
+            $(pub const $name: HResult = HResult::from_u32($value);)*
+        }
+
+        const MESSAGES: &[(u32, &str, &str)] = &[
+            $(($value, stringify!($name), $description),)*
+        ];
+    };
+}
+
+message_table! {
+    S_OK = 0x0000_0000, "Success.";
+    S_FALSE = 0x0000_0001, "Success, with a false or partial result.";
+
+    E_UNEXPECTED = 0x8000_FFFF, "Catastrophic failure.";
+    E_NOTIMPL = 0x8000_4001, "Not implemented.";
+    E_NOINTERFACE = 0x8000_4002, "No such interface supported.";
+    E_POINTER = 0x8000_4003, "Invalid pointer.";
+    E_ABORT = 0x8000_4004, "Operation aborted.";
+    E_FAIL = 0x8000_4005, "Unspecified error.";
+    E_ACCESSDENIED = 0x8007_0005, "Access is denied.";
+    E_HANDLE = 0x8007_0006, "Invalid handle.";
+    E_OUTOFMEMORY = 0x8007_000E, "Not enough memory.";
+    E_INVALIDARG = 0x8007_0057, "One or more arguments are invalid.";
+
+    CLASS_E_NOAGGREGATION = 0x8004_0110, "Class does not support aggregation.";
+    CLASS_E_CLASSNOTAVAILABLE = 0x8004_0111, "Class factory cannot supply the requested class.";
+    REGDB_E_CLASSNOTREG = 0x8004_0154, "Class not registered.";
+    REGDB_E_IIDNOTREG = 0x8004_0155, "Interface not registered.";
+
+    CO_E_NOTINITIALIZED = 0x8004_01F0, "CoInitialize has not been called.";
+    CO_E_ALREADYINITIALIZED = 0x8004_01F1, "CoInitialize has already been called.";
+    CO_E_CLASSSTRING = 0x8004_01F3, "Invalid class string.";
+    CO_E_APPNOTFOUND = 0x8004_01F5, "Application not found.";
+    CO_E_DLLNOTFOUND = 0x8004_01F8, "DLL for class not found.";
+    CO_E_ERRORINDLL = 0x8004_01F9, "Error in the DLL.";
+    CO_E_OBJNOTCONNECTED = 0x8004_01FD, "Object is not connected to server.";
+    CO_E_SERVER_EXEC_FAILURE = 0x8008_0005, "Server execution failed.";
+    CO_E_SERVER_STOPPING = 0x8008_0008, "Object server is stopping when OLE service contacts it.";
+
+    OLE_E_NOTRUNNING = 0x8004_0005, "Object is not running.";
+    OLE_E_BLANK = 0x8004_0007, "Uninitialized object.";
+    OLE_E_CANT_BINDTOSOURCE = 0x8004_000A, "Not able to bind to the source.";
+    OLE_E_STATIC = 0x8004_000B, "Object is static; operation not allowed.";
+    OLE_E_WRONGCOMPOBJ = 0x8004_000E, "compobj.dll is too old for the ole2.dll initialized.";
+
+    CONNECT_E_NOCONNECTION = 0x8004_0200, "No connection for this connection ID.";
+    CONNECT_E_ADVISELIMIT = 0x8004_0201, "Limit of connections has been reached.";
+    CONNECT_E_CANNOTCONNECT = 0x8004_0202, "Source interface is not supported by the sink.";
+
+    MK_E_CONNECTMANUALLY = 0x8004_01E0, "Operation unavailable; connect manually.";
+    MK_E_EXCEEDEDDEADLINE = 0x8004_01E1, "Binding did not complete before the deadline.";
+    MK_E_UNAVAILABLE = 0x8004_01E3, "Operation unavailable.";
+    MK_E_SYNTAX = 0x8004_01E4, "Invalid syntax.";
+    MK_E_NOOBJECT = 0x8004_01E5, "No object for moniker.";
+    MK_E_INVALIDEXTENSION = 0x8004_01E6, "Bad extension for file.";
+    MK_E_CANTOPENFILE = 0x8004_01EA, "Moniker cannot open file.";
+
+    DISP_E_UNKNOWNINTERFACE = 0x8002_0001, "Unknown interface.";
+    DISP_E_MEMBERNOTFOUND = 0x8002_0003, "Member not found.";
+    DISP_E_PARAMNOTFOUND = 0x8002_0004, "Parameter not found.";
+    DISP_E_TYPEMISMATCH = 0x8002_0005, "Type mismatch.";
+    DISP_E_UNKNOWNNAME = 0x8002_0006, "Unknown name.";
+    DISP_E_NONAMEDARGS = 0x8002_0007, "No named arguments.";
+    DISP_E_BADVARTYPE = 0x8002_0008, "Bad variable type.";
+    DISP_E_EXCEPTION = 0x8002_0009, "Exception occurred.";
+    DISP_E_OVERFLOW = 0x8002_000A, "Out of present range.";
+    DISP_E_BADINDEX = 0x8002_000B, "Invalid index.";
+    DISP_E_UNKNOWNLCID = 0x8002_000C, "Unknown language.";
+    DISP_E_ARRAYISLOCKED = 0x8002_000D, "Memory is locked.";
+    DISP_E_BADPARAMCOUNT = 0x8002_000E, "Invalid number of parameters.";
+    DISP_E_PARAMNOTOPTIONAL = 0x8002_000F, "Parameter not optional.";
+    DISP_E_BADCALLEE = 0x8002_0010, "Invalid callee.";
+    DISP_E_NOTACOLLECTION = 0x8002_0011, "Does not support a collection.";
+    DISP_E_DIVBYZERO = 0x8002_0012, "Division by zero.";
+
+    TYPE_E_LIBNOTREGISTERED = 0x8002_801D, "Library not registered.";
+    TYPE_E_ELEMENTNOTFOUND = 0x8002_802B, "Element not found.";
+    TYPE_E_TYPEMISMATCH = 0x8002_8CA0, "Type mismatch.";
+    TYPE_E_CANTLOADLIBRARY = 0x8002_9C4A, "Error loading type library/DLL.";
+
+    RPC_E_CALL_REJECTED = 0x8001_0001, "Call was rejected by callee.";
+    RPC_E_CALL_CANCELED = 0x8001_0002, "Call was canceled by the message filter.";
+    RPC_E_CANTCALLOUT_INASYNCCALL = 0x8001_0004, "The caller is dispatching an asynchronous call and cannot make an outgoing call on behalf of this call.";
+    RPC_E_CANTCALLOUT_INEXTERNALCALL = 0x8001_0005, "It is illegal to call out while inside message filter.";
+    RPC_E_CONNECTION_TERMINATED = 0x8001_0006, "The connection terminated or is in a bogus state and cannot be used any more.";
+    RPC_E_SERVER_DIED = 0x8001_0007, "The callee (server) is not available and disappeared; all connections are invalid.";
+    RPC_E_SERVER_DIED_DNE = 0x8001_0012, "The callee (server) is not available and disappeared; the call did not execute.";
+    RPC_E_SERVERFAULT = 0x8001_0105, "The server threw an exception.";
+    RPC_E_CHANGED_MODE = 0x8001_0106, "Cannot change thread mode after it is set.";
+    RPC_E_DISCONNECTED = 0x8001_0108, "The object invoked has disconnected from its clients.";
+    RPC_E_SERVERCALL_RETRYLATER = 0x8001_010A, "The message filter indicated that the application is busy.";
+    RPC_E_SERVERCALL_REJECTED = 0x8001_010B, "The message filter rejected the call.";
+    RPC_E_CANTCALLOUT_ININPUTSYNCCALL = 0x8001_010D, "An outgoing call cannot be made since the application is dispatching an input-synchronous call.";
+    RPC_E_WRONG_THREAD = 0x8001_010E, "The application called an interface that was marshalled for a different thread.";
+    RPC_E_TIMEOUT = 0x8001_011F, "This operation returned because the timeout period expired.";
+    RPC_S_SERVER_UNAVAILABLE = 0x8007_06BA, "The RPC server is unavailable.";
+    RPC_S_CALL_FAILED = 0x8007_06BE, "The remote procedure call failed.";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_win32_round_trips() {
+        for code in [1, 5, 87, 0x06BA, 0xFFFF] {
+            let hresult = HResult::from_win32(code);
+            assert!(hresult.is_error());
+            assert_eq!(hresult.facility_name(), Some("FACILITY_WIN32"));
+            assert_eq!(hresult.win32_code(), Some(code));
+        }
+        assert_eq!(HResult::from_win32(0), HResult::S_OK);
+        assert_eq!(HResult::from_win32(5), HResult::E_ACCESSDENIED);
+        assert_eq!(
+            HResult::from_win32(0x06BA),
+            HResult::RPC_S_SERVER_UNAVAILABLE
+        );
+        // Values that already are HRESULTs pass through unchanged.
+        assert_eq!(HResult::from_win32(0x8002_0009), HResult::DISP_E_EXCEPTION);
+        assert_eq!(HResult::DISP_E_EXCEPTION.win32_code(), None);
+    }
+
+    #[test]
+    fn decodes_fields() {
+        let hresult = HResult::DISP_E_EXCEPTION;
+        assert_eq!(hresult.severity(), Severity::Error);
+        assert_eq!(hresult.facility(), 2);
+        assert_eq!(hresult.facility_name(), Some("FACILITY_DISPATCH"));
+        assert_eq!(hresult.code(), 9);
+        assert_eq!(HResult::S_FALSE.severity(), Severity::Success);
+        assert_eq!(HResult::from_u32(0x8123_0000).facility_name(), None);
+    }
+
+    #[test]
+    fn looks_up_table_entries() {
+        assert_eq!(
+            HResult::RPC_E_CALL_REJECTED.name(),
+            Some("RPC_E_CALL_REJECTED")
+        );
+        assert_eq!(
+            HResult::REGDB_E_CLASSNOTREG.description(),
+            Some("Class not registered.")
+        );
+        assert_eq!(HResult::from_u32(0x8004_0154), HResult::REGDB_E_CLASSNOTREG);
+        assert_eq!(HResult::from_u32(0x8123_4567).name(), None);
+        assert_eq!(
+            HResult::E_FAIL.to_string(),
+            "0x80004005 (E_FAIL: Unspecified error.)"
+        );
+        assert_eq!(HResult::from_u32(0x8123_4567).to_string(), "0x81234567");
+    }
+
+    #[test]
+    fn table_values_are_unique() {
+        for (index, (value, name, _)) in MESSAGES.iter().enumerate() {
+            assert!(
+                MESSAGES[index + 1..].iter().all(|entry| entry.0 != *value),
+                "{name} is listed twice"
+            );
+        }
+    }
+
+    #[test]
+    fn retryable_codes() {
+        assert!(HResult::RPC_E_CALL_REJECTED.is_retryable());
+        assert!(HResult::RPC_E_SERVERCALL_RETRYLATER.is_retryable());
+        assert!(!HResult::RPC_E_SERVER_DIED.is_retryable());
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use windows::{
+    core::GUID,
+    Win32::System::{Com::TKIND_DISPATCH, Ole::TYPEFLAG_FDUAL},
+};
+
+use crate::{
+    error::Result,
+    olemethoddata::{ole_declared_methods, OleMethodData},
+    util::ole::TypeRef,
+    OleTypeData,
+};
+
+/// A member reachable on an interface, together with the interface in the
+/// inheritance chain that declares it.
+#[derive(Debug)]
+pub struct InheritedMember {
+    method: OleMethodData,
+    declaring_interface: String,
+    declaring_guid: GUID,
+    depth: usize,
+    shadowed_by: Option<String>,
+}
+
+impl InheritedMember {
+    pub fn method(&self) -> &OleMethodData {
+        &self.method
+    }
+    pub fn into_method(self) -> OleMethodData {
+        self.method
+    }
+    pub fn name(&self) -> &str {
+        self.method.name()
+    }
+    /// Name of the interface that declares this member.
+    ///
+    pub fn declaring_interface(&self) -> &str {
+        &self.declaring_interface
+    }
+    pub fn declaring_guid(&self) -> GUID {
+        self.declaring_guid
+    }
+    /// Distance from the interface the walk started at: `0` for its own
+    /// members, `1` for its base interface and so on.
+    ///
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+    pub fn vtable_slot(&self) -> Option<usize> {
+        self.method.vtable_slot()
+    }
+    /// Name of the more derived interface that redeclares this member, if any.
+    ///
+    pub fn shadowed_by(&self) -> Option<&str> {
+        self.shadowed_by.as_deref()
+    }
+    pub fn is_shadowed(&self) -> bool {
+        self.shadowed_by.is_some()
+    }
+}
+
+/// Walk from `ole_type` to the root of its inheritance chain, following the
+/// first implemented type of each interface.
+///
+/// A dual dispinterface is replaced by its vtable interface so that every
+/// interface in the chain only reports the members it declares itself.
+pub(crate) fn inheritance_chain(ole_type: &OleTypeData) -> Result<Vec<OleTypeData>> {
+    let mut current = if ole_type.typekind() == TKIND_DISPATCH
+        && ole_type.attribs().wTypeFlags & TYPEFLAG_FDUAL.0 as u16 != 0
+    {
+        ole_type.get_interface_of_dispinterface()?
+    } else {
+        OleTypeData::try_from(ole_type.typeinfo().clone())?
+    };
+
+    let mut seen = HashSet::new();
+    let mut chain = vec![];
+    loop {
+        if !seen.insert(current.guid()) {
+            break;
+        }
+        let next = if current.num_impl_types() > 0 {
+            let ref_type = unsafe { current.typeinfo().GetRefTypeOfImplType(0)? };
+            Some(current.get_ref_type_info(ref_type)?)
+        } else {
+            None
+        };
+        chain.push(current);
+        match next {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    Ok(chain)
+}
+
+/// Every member available through `ole_type`, ordered from the root interface
+/// down to `ole_type` itself, so that vtable members appear in slot order.
+pub(crate) fn flattened_members(ole_type: &OleTypeData) -> Result<Vec<InheritedMember>> {
+    let chain = inheritance_chain(ole_type)?;
+
+    let mut declared_by: HashMap<(String, i32), String> = HashMap::new();
+    let mut levels = Vec::with_capacity(chain.len());
+    for (depth, interface) in chain.iter().enumerate() {
+        let mut level = vec![];
+        for method in ole_declared_methods(interface.typeinfo())? {
+            let key = (method.name().to_lowercase(), method.invkind().0);
+            let shadowed_by = declared_by.get(&key).cloned();
+            if shadowed_by.is_none() {
+                declared_by.insert(key, interface.name().to_string());
+            }
+            level.push(InheritedMember {
+                method,
+                declaring_interface: interface.name().to_string(),
+                declaring_guid: interface.guid(),
+                depth,
+                shadowed_by,
+            });
+        }
+        levels.push(level);
+    }
+
+    Ok(levels.into_iter().rev().flatten().collect())
+}
@@ -2,29 +2,67 @@ use crate::{error::Result, util::RegKey};
 use std::sync::LazyLock;
 use windows::Win32::System::Registry::{HKEY_CLASSES_ROOT, HKEY_LOCAL_MACHINE};
 
+mod agile;
+mod apartment;
+mod coclass;
+mod custdata;
+mod dependencies;
+mod dispatchex;
+mod dispcall;
+mod dllentry;
+mod documentation;
 pub mod error;
+mod hresult;
+mod inheritance;
+mod moniker;
+mod navigate;
 mod oledata;
+mod oleenum;
 //mod oleeventdata;
 mod olemethoddata;
 mod oleparamdata;
 mod oletypedata;
 mod oletypelibdata;
+mod olevalue;
 mod olevariabledata;
+mod retry;
+mod rot;
+mod tracker;
 pub mod types;
 mod util;
+mod worker;
 //mod variant;
 
 pub use {
+    agile::AgileOleData,
+    apartment::{current_apartment, Apartment, ApartmentBuilder, ApartmentType},
+    custdata::CustomData,
+    dependencies::{
+        ExternalTypeRef, LibraryDependency, TypeLibDependencies, TypeRefKind, UnresolvedTypeRef,
+    },
+    dispatchex::{DispatchEx, DynamicMember},
+    dllentry::{DllEntry, EntryPoint},
+    documentation::{Documentation, Documented, LocalizedDocumentation},
+    error::ExceptionInfo,
+    hresult::{HResult, Severity},
+    inheritance::InheritedMember,
+    moniker::{bind_moniker, parse_display_name, BindOptions},
     oledata::OleData,
-    olemethoddata::OleMethodData,
+    oleenum::OleEnum,
+    olemethoddata::{FuncFlags, OleMethodData},
     oleparamdata::OleParamData,
     oletypedata::OleTypeData,
     oletypelibdata::{oletypelib_from_guid, OleTypeLibData},
+    olevalue::OleValue,
     olevariabledata::OleVariableData,
+    retry::{MessageFilterGuard, RetryPolicy},
+    rot::{running_objects, RunningObject},
+    tracker::{live_objects, LiveObject},
     util::{
         conv::ToWide,
-        ole::{init_runtime, ole_initialized, TypeRef},
+        ole::{init_runtime, TypeRef},
     },
+    worker::{RemoteObject, RemoteValue, Response, StaWorker, StaWorkerBuilder, WorkerHandle},
 };
 
 static G_RUNNING_NANO: LazyLock<bool> = LazyLock::new(|| {
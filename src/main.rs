@@ -1,6 +1,7 @@
 use windows::Win32::System::Variant::VARIANT;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _apartment = win32ole::Apartment::ole()?;
     let excel_app = win32ole::OleData::new("Excel.Application")?;
     let mut vt_true = VARIANT::from(true);
 
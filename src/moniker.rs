@@ -0,0 +1,131 @@
+use std::{ffi::OsStr, mem, time::Duration};
+
+use windows::{
+    core::PCWSTR,
+    Win32::System::{
+        Com::{
+            CreateBindCtx, IBindCtx, IDispatch, IMoniker, MkParseDisplayName, BIND_FLAGS,
+            BIND_OPTS, STGM, STGM_READWRITE,
+        },
+        SystemInformation::GetTickCount,
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    error::{Error, Result},
+    OleData, ToWide,
+};
+
+/// Options passed to the bind context when binding a moniker.
+#[derive(Debug, Clone, Copy)]
+pub struct BindOptions {
+    flags: BIND_FLAGS,
+    mode: STGM,
+    timeout: Option<Duration>,
+}
+
+impl Default for BindOptions {
+    fn default() -> Self {
+        BindOptions {
+            flags: BIND_FLAGS(0),
+            mode: STGM_READWRITE,
+            timeout: None,
+        }
+    }
+}
+
+impl BindOptions {
+    pub fn new() -> BindOptions {
+        BindOptions::default()
+    }
+    /// `BIND_MAYBOTHERUSER` and/or `BIND_JUSTTESTEXISTENCE`.
+    ///
+    pub fn with_flags(mut self, flags: BIND_FLAGS) -> BindOptions {
+        self.flags = flags;
+        self
+    }
+    /// Access mode used when the bound object has to be opened, e.g. a file.
+    ///
+    pub fn with_mode(mut self, mode: STGM) -> BindOptions {
+        self.mode = mode;
+        self
+    }
+    /// Give up with `MK_E_EXCEEDEDDEADLINE` if binding takes longer than `timeout`.
+    ///
+    pub fn with_timeout(mut self, timeout: Duration) -> BindOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+    pub fn flags(&self) -> BIND_FLAGS {
+        self.flags
+    }
+    pub fn mode(&self) -> STGM {
+        self.mode
+    }
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+    fn bind_opts(&self) -> BIND_OPTS {
+        let deadline = match self.timeout {
+            // A deadline of 0 means no deadline, so never produce it by accident.
+            Some(timeout) => {
+                let millis = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+                unsafe { GetTickCount() }.wrapping_add(millis).max(1)
+            }
+            None => 0,
+        };
+        BIND_OPTS {
+            cbStruct: mem::size_of::<BIND_OPTS>() as u32,
+            grfFlags: self.flags.0 as u32,
+            grfMode: self.mode.0,
+            dwTickCountDeadline: deadline,
+        }
+    }
+}
+
+/// Parse a display name such as `winmgmts:\\.\root\cimv2` or
+/// `C:\book.xlsx` into a moniker.
+///
+/// A failure is reported as [`Error::MonikerSyntax`] with the number of
+/// UTF-16 units that were consumed before parsing stopped.
+pub fn parse_display_name<S: AsRef<OsStr>>(display_name: S) -> Result<IMoniker> {
+    parse_with(display_name.as_ref(), &BindOptions::default()).map(|(moniker, _)| moniker)
+}
+
+/// Parse `display_name` and bind the resulting moniker to an automation object.
+#[track_caller]
+pub fn bind_moniker<S: AsRef<OsStr>>(display_name: S, options: &BindOptions) -> Result<OleData> {
+    let (moniker, bind_ctx) = parse_with(display_name.as_ref(), options)?;
+    let dispatch: IDispatch = unsafe { moniker.BindToObject(&bind_ctx, None)? };
+    Ok(OleData::from_dispatch(dispatch))
+}
+
+fn parse_with(display_name: &OsStr, options: &BindOptions) -> Result<(IMoniker, IBindCtx)> {
+    ensure_apartment()?;
+    let bind_ctx = unsafe { CreateBindCtx(0)? };
+    unsafe { bind_ctx.SetBindOptions(&options.bind_opts())? };
+
+    let name = display_name.to_wide_null();
+    let mut eaten = 0;
+    let mut moniker = None;
+    let result = unsafe {
+        MkParseDisplayName(
+            &bind_ctx,
+            PCWSTR::from_raw(name.as_ptr()),
+            &mut eaten,
+            &mut moniker,
+        )
+    };
+    if let Err(source) = result {
+        return Err(Error::MonikerSyntax {
+            display_name: display_name.to_string_lossy().into_owned(),
+            position: eaten as usize,
+            source,
+        });
+    }
+    match moniker {
+        Some(moniker) => Ok((moniker, bind_ctx)),
+        None => Err(Error::Generic("MkParseDisplayName returned a null moniker")),
+    }
+}
@@ -0,0 +1,336 @@
+use windows::Win32::System::{
+    Com::{DISPATCH_METHOD, DISPATCH_PROPERTYGET},
+    Variant::VARIANT,
+};
+
+use crate::{
+    error::{Error, Result},
+    olevalue::OleValue,
+    OleData,
+};
+
+/// One `Name` or `Name(args)` step of a property path.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Segment {
+    pub(crate) name: String,
+    pub(crate) args: Vec<OleValue>,
+    /// Character offset of the segment within the path.
+    pub(crate) position: usize,
+    /// The segment as written, e.g. `Range("A1")`.
+    pub(crate) text: String,
+}
+
+/// Evaluate `path` against `object`, one segment at a time.
+#[track_caller]
+pub(crate) fn navigate(object: &OleData, path: &str) -> Result<OleValue> {
+    let segments = parse_path(path)?;
+    let mut current = OleValue::Object(object.clone());
+    for segment in segments {
+        let fail = |source: Error| Error::Navigation {
+            path: path.to_string(),
+            segment: segment.text.clone(),
+            position: segment.position,
+            source: Box::new(source),
+        };
+        let Some(object) = current.as_object() else {
+            return Err(fail(Error::Custom(format!(
+                "cannot access `{}` on a value that is not an object ({current})",
+                segment.name
+            ))));
+        };
+        current = evaluate(object, &segment).map_err(fail)?;
+    }
+    Ok(current)
+}
+
+#[track_caller]
+fn evaluate(object: &OleData, segment: &Segment) -> Result<OleValue> {
+    let args: Vec<VARIANT> = segment.args.iter().map(VARIANT::from).collect();
+    let result = if args.is_empty() {
+        // Like VB, a bare name may be either a property or a method.
+        let dispid = object.dispid(&segment.name)?;
+        object.call_dispid(dispid, args, DISPATCH_METHOD | DISPATCH_PROPERTYGET)?
+    } else {
+        object.get_with_args(&segment.name, args)?
+    };
+    OleValue::try_from(result)
+}
+
+pub(crate) fn parse_path(path: &str) -> Result<Vec<Segment>> {
+    Parser {
+        path,
+        chars: path.chars().collect(),
+        pos: 0,
+    }
+    .parse()
+}
+
+struct Parser<'a> {
+    path: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> Result<Vec<Segment>> {
+        let mut segments = vec![];
+        loop {
+            self.skip_whitespace();
+            segments.push(self.segment()?);
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some('.') => self.pos += 1,
+                Some(_) => return Err(self.error("expected `.` or end of path")),
+            }
+        }
+        Ok(segments)
+    }
+
+    fn segment(&mut self) -> Result<Segment> {
+        let start = self.pos;
+        let name = self.identifier()?;
+        let mut end = self.pos;
+        let mut args = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                self.pos += 1;
+            } else {
+                loop {
+                    self.skip_whitespace();
+                    args.push(self.literal()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.pos += 1,
+                        Some(')') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => return Err(self.error("expected `,` or `)`")),
+                    }
+                }
+            }
+            end = self.pos;
+        }
+        Ok(Segment {
+            name,
+            args,
+            position: start,
+            text: self.chars[start..end].iter().collect(),
+        })
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => {}
+            _ => return Err(self.error("expected a member name")),
+        }
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn literal(&mut self) -> Result<OleValue> {
+        match self.peek() {
+            Some('"') => self.string(),
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() => {
+                let start = self.pos;
+                let word = self.identifier()?;
+                if word.eq_ignore_ascii_case("true") {
+                    Ok(OleValue::Bool(true))
+                } else if word.eq_ignore_ascii_case("false") {
+                    Ok(OleValue::Bool(false))
+                } else {
+                    self.pos = start;
+                    Err(self.error("expected a string, number or boolean literal"))
+                }
+            }
+            _ => Err(self.error("expected a string, number or boolean literal")),
+        }
+    }
+
+    /// A double-quoted string; `""` inside it stands for one quote, as in VB.
+    fn string(&mut self) -> Result<OleValue> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => {
+                    self.pos = start;
+                    return Err(self.error("unterminated string literal"));
+                }
+                Some('"') if self.chars.get(self.pos + 1) == Some(&'"') => {
+                    value.push('"');
+                    self.pos += 2;
+                }
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(OleValue::String(value));
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// An integer becomes `VT_I4` when it fits and `VT_R8` otherwise.
+    fn number(&mut self) -> Result<OleValue> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.pos += 1;
+        }
+        let mut is_float = false;
+        while let Some(c) = self.peek() {
+            match c {
+                '0'..='9' => {}
+                '.' => is_float = true,
+                'e' | 'E' => {
+                    is_float = true;
+                    if matches!(self.chars.get(self.pos + 1), Some('-' | '+')) {
+                        self.pos += 1;
+                    }
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if !is_float {
+            if let Ok(value) = text.parse::<i32>() {
+                return Ok(OleValue::I4(value));
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(value) => Ok(OleValue::R8(value)),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("invalid number literal"))
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, message: &'static str) -> Error {
+        Error::PathSyntax {
+            path: self.path.to_string(),
+            position: self.pos,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syntax_error(path: &str) -> (usize, &'static str) {
+        match parse_path(path) {
+            Err(Error::PathSyntax {
+                position, message, ..
+            }) => (position, message),
+            other => panic!("expected a syntax error for `{path}`, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_segments_with_arguments() {
+        let segments = parse_path(r#"A.B(1,"x").C"#).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment {
+                    name: "A".into(),
+                    args: vec![],
+                    position: 0,
+                    text: "A".into(),
+                },
+                Segment {
+                    name: "B".into(),
+                    args: vec![OleValue::I4(1), OleValue::String("x".into())],
+                    position: 2,
+                    text: r#"B(1,"x")"#.into(),
+                },
+                Segment {
+                    name: "C".into(),
+                    args: vec![],
+                    position: 11,
+                    text: "C".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_literals() {
+        let segments =
+            parse_path("Cells( -1 , 2.5, 3e2, 4000000000, True, false ) . Value()").unwrap();
+        assert_eq!(
+            segments[0].args,
+            vec![
+                OleValue::I4(-1),
+                OleValue::R8(2.5),
+                OleValue::R8(300.0),
+                OleValue::R8(4000000000.0),
+                OleValue::Bool(true),
+                OleValue::Bool(false),
+            ]
+        );
+        assert_eq!(segments[1].name, "Value");
+        assert_eq!(segments[1].text, "Value()");
+        assert!(segments[1].args.is_empty());
+    }
+
+    #[test]
+    fn doubled_quotes_stand_for_one_quote() {
+        let segments = parse_path(r#"Range("say ""hi""", "")"#).unwrap();
+        assert_eq!(
+            segments[0].args,
+            vec![
+                OleValue::String(r#"say "hi""#.into()),
+                OleValue::String(String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert_eq!(syntax_error("A B"), (2, "expected `.` or end of path"));
+        assert_eq!(syntax_error("A(1 2)"), (4, "expected `,` or `)`"));
+        assert_eq!(syntax_error("A(1"), (3, "expected `,` or `)`"));
+        assert_eq!(syntax_error(""), (0, "expected a member name"));
+        assert_eq!(syntax_error("A..B"), (2, "expected a member name"));
+        assert_eq!(syntax_error("1A"), (0, "expected a member name"));
+        assert_eq!(
+            syntax_error("A(x)"),
+            (2, "expected a string, number or boolean literal")
+        );
+        assert_eq!(
+            syntax_error("A(1,)"),
+            (4, "expected a string, number or boolean literal")
+        );
+        assert_eq!(
+            syntax_error(r#"A("abc)"#),
+            (2, "unterminated string literal")
+        );
+        assert_eq!(syntax_error("A(1.2.3)"), (2, "invalid number literal"));
+        assert_eq!(syntax_error("A(-)"), (2, "invalid number literal"));
+    }
+}
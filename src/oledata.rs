@@ -1,33 +1,57 @@
-use std::{ffi::OsStr, ptr};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::{c_void, OsStr},
+    fmt, ptr, slice,
+    sync::Arc,
+};
 
 use windows::{
     core::{Interface, BSTR, GUID, PCWSTR},
     Win32::{
-        Foundation::{DISP_E_EXCEPTION, DISP_E_PARAMNOTFOUND, DISP_E_TYPEMISMATCH},
+        Foundation::{
+            DISP_E_BADPARAMCOUNT, DISP_E_EXCEPTION, DISP_E_PARAMNOTFOUND, DISP_E_TYPEMISMATCH,
+        },
         Globalization::GetUserDefaultLCID,
         System::{
             Com::{
                 IDispatch, ITypeInfo, ITypeLib, DISPATCH_FLAGS, DISPATCH_METHOD,
-                DISPATCH_PROPERTYGET, DISPATCH_PROPERTYPUT, DISPPARAMS, EXCEPINFO, INVOKE_FUNC,
-                INVOKE_PROPERTYGET, INVOKE_PROPERTYPUT, INVOKE_PROPERTYPUTREF,
+                DISPATCH_PROPERTYGET, DISPATCH_PROPERTYPUT, DISPATCH_PROPERTYPUTREF, DISPPARAMS,
+                EXCEPINFO, INVOKE_FUNC, INVOKE_PROPERTYGET, INVOKE_PROPERTYPUT,
+                INVOKE_PROPERTYPUTREF,
             },
-            Ole::DISPID_PROPERTYPUT,
-            Variant::VARIANT,
+            Ole::{DISPID_NEWENUM, DISPID_PROPERTYPUT},
+            Variant::{VARIANT, VT_BYREF, VT_DISPATCH, VT_UNKNOWN},
         },
     },
 };
 
 use crate::{
-    error::{ComArgumentErrorType, Error, OleError, Result},
+    agile::AgileOleData,
+    apartment::ensure_apartment,
+    coclass,
+    dispatchex::DispatchEx,
+    dispcall,
+    error::{ComArgumentErrorType, Error, ExceptionInfo, OleError, Result},
+    moniker::{bind_moniker, BindOptions},
+    navigate,
+    oleenum::OleEnum,
     olemethoddata::{ole_methods_from_typeinfo, OleMethodData},
+    olevalue::OleValue,
+    retry::{message_filter_registered, RetryPolicy},
+    tracker::Tracker,
     types::OleClassNames,
     util::{
         conv::ToWide,
-        ole::{create_com_object, get_class_id},
+        ole::{create_com_object, get_active_object, get_class_id},
     },
     OleTypeData, OleTypeLibData,
 };
 
+/// The default member. The `windows` crate declares it as a `u32`, while
+/// DISPIDs are `i32` everywhere else.
+const DISPID_VALUE: i32 = 0;
+
 /*#[inline]
 pub unsafe fn ShowHTMLDialogEx<P0, P1>(
     hwndparent: P0,
@@ -47,22 +71,126 @@ where
 
 pub struct OleData {
     pub dispatch: IDispatch,
+    dispids: RefCell<HashMap<String, i32>>,
+    /// Whether the member with a DISPID has a `propputref` accessor.
+    putref_dispids: RefCell<HashMap<i32, bool>>,
+    retry: Option<Arc<RetryPolicy>>,
+    clsid: Option<GUID>,
+    _tracker: Tracker,
 }
 impl OleData {
+    #[track_caller]
     pub fn new<S: AsRef<OsStr>>(prog_id: S) -> Result<Self> {
-        Ok(OleData {
-            dispatch: create_com_object(prog_id)?,
-        })
+        let clsid = get_class_id(&prog_id).ok();
+        let mut object = OleData::from_dispatch(create_com_object(prog_id)?);
+        object.clsid = clsid;
+        Ok(object)
+    }
+    /// Attach to an already running instance of `prog_id`, like `WIN32OLE.connect`
+    ///
+    #[track_caller]
+    pub fn connect<S: AsRef<OsStr>>(prog_id: S) -> Result<Self> {
+        let clsid = get_class_id(&prog_id).ok();
+        let mut object = OleData::from_dispatch(get_active_object(prog_id)?);
+        object.clsid = clsid;
+        Ok(object)
     }
-    pub fn get_ids_of_names<S: AsRef<OsStr> + Copy>(&self, names: &[S]) -> Result<Vec<i32>> {
-        let namelen = names.len();
-        let mut wnames = vec![PCWSTR::null(); namelen];
-        for i in 0..namelen {
-            let a = names[i].to_wide_null();
-            wnames[i] = PCWSTR(a.as_ptr());
+    /// Bind to the object named by a moniker display name, e.g.
+    /// `winmgmts:\\.\root\cimv2` or the path of a document
+    ///
+    #[track_caller]
+    pub fn bind<S: AsRef<OsStr>>(display_name: S) -> Result<Self> {
+        bind_moniker(display_name, &BindOptions::default())
+    }
+    #[track_caller]
+    pub fn bind_with_options<S: AsRef<OsStr>>(
+        display_name: S,
+        options: &BindOptions,
+    ) -> Result<Self> {
+        bind_moniker(display_name, options)
+    }
+    #[track_caller]
+    pub fn from_dispatch(dispatch: IDispatch) -> OleData {
+        OleData {
+            _tracker: Tracker::new(dispatch.as_raw() as usize),
+            dispatch,
+            dispids: RefCell::new(HashMap::new()),
+            putref_dispids: RefCell::new(HashMap::new()),
+            retry: RetryPolicy::default_policy(),
+            clsid: None,
         }
-
-        let mut dispids = 0;
+    }
+    /// Take ownership of a raw `IDispatch` pointer, e.g. one produced by
+    /// [`OleData::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid `IDispatch` pointer carrying a reference that the
+    /// returned `OleData` now owns.
+    ///
+    #[track_caller]
+    pub unsafe fn from_raw(raw: *mut c_void) -> OleData {
+        OleData::from_dispatch(IDispatch::from_raw(raw))
+    }
+    /// Give up ownership of the object's reference without releasing it.
+    ///
+    pub fn into_raw(self) -> *mut c_void {
+        self.dispatch.into_raw()
+    }
+    /// Release this reference to the object now, like `WIN32OLE#ole_free`.
+    ///
+    /// Every clone holds its own reference, and the server only exits once all
+    /// of them are released. Returns the object's remaining reference count,
+    /// which is only a diagnostic value.
+    ///
+    pub fn release(self) -> u32 {
+        let release = self.dispatch.vtable().base__.Release;
+        let raw = self.into_raw();
+        unsafe { release(raw) }
+    }
+    /// The object's current reference count, like `WIN32OLE#ole_reference_count`.
+    ///
+    /// For an out-of-process server this is the count on the local proxy.
+    ///
+    pub fn reference_count(&self) -> u32 {
+        let raw = self.dispatch.as_raw();
+        let unknown = &self.dispatch.vtable().base__;
+        unsafe {
+            (unknown.AddRef)(raw);
+            (unknown.Release)(raw)
+        }
+    }
+    /// Retry calls on this object that fail with a retryable HRESULT, such as
+    /// `RPC_E_CALL_REJECTED` while Excel is busy. `None` disables retries.
+    ///
+    /// Objects start out with [`RetryPolicy::default_policy`]. The policy is
+    /// not applied while a [`MessageFilterGuard`](crate::MessageFilterGuard)
+    /// is registered on the calling thread, since COM already retries then.
+    ///
+    pub fn with_retry_policy(mut self, policy: Option<RetryPolicy>) -> OleData {
+        self.set_retry_policy(policy);
+        self
+    }
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy.map(Arc::new);
+    }
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_deref()
+    }
+    /// Resolve a member name followed by the names of its parameters with a
+    /// single `GetIDsOfNames` call. The result holds one DISPID per name.
+    ///
+    pub fn get_ids_of_names<S: AsRef<OsStr>>(&self, names: &[S]) -> Result<Vec<i32>> {
+        ensure_apartment()?;
+        self.retrying(|| self.get_ids_of_names_once(names))
+    }
+    fn get_ids_of_names_once<S: AsRef<OsStr>>(&self, names: &[S]) -> Result<Vec<i32>> {
+        let wide_names: Vec<Vec<u16>> = names.iter().map(|name| name.to_wide_null()).collect();
+        let wnames: Vec<PCWSTR> = wide_names
+            .iter()
+            .map(|name| PCWSTR(name.as_ptr()))
+            .collect();
+        let mut dispids = vec![0; wnames.len()];
 
         unsafe {
             self.dispatch.GetIDsOfNames(
@@ -70,30 +198,39 @@ impl OleData {
                 wnames.as_ptr(),
                 wnames.len() as u32,
                 GetUserDefaultLCID(),
-                &mut dispids,
+                dispids.as_mut_ptr(),
             )
         }?;
 
-        let ids = unsafe { Vec::from_raw_parts(&mut dispids, wnames.len(), wnames.len()) };
-
-        Ok(ids)
+        Ok(dispids)
     }
-    pub fn responds_to<S: AsRef<OsStr>>(&self, method: S) -> bool {
-        let method = method.to_wide_null();
-        let methods = [PCWSTR(method.as_ptr())];
-        let mut dispids = 0;
-
-        unsafe {
-            self.dispatch
-                .GetIDsOfNames(
-                    &GUID::zeroed(),
-                    methods.as_ptr(),
-                    1,
-                    GetUserDefaultLCID(),
-                    &mut dispids,
-                )
-                .is_ok()
+    /// Resolve the DISPID of a member, using the per-object cache.
+    ///
+    /// Names are cached case-insensitively, matching `IDispatch` name lookup.
+    ///
+    pub fn dispid<S: AsRef<OsStr>>(&self, name: S) -> Result<i32> {
+        let key = name.as_ref().to_string_lossy().to_lowercase();
+        if let Some(dispid) = self.dispids.borrow().get(&key) {
+            return Ok(*dispid);
         }
+        let dispid = self.get_ids_of_names(&[name])?[0];
+        self.dispids.borrow_mut().insert(key, dispid);
+        Ok(dispid)
+    }
+    /// Resolve the DISPIDs of several members. Every name that is not cached
+    /// yet costs its own `GetIDsOfNames` round-trip: `IDispatch` reads all but
+    /// the first name of one call as parameter names, so members cannot be
+    /// batched.
+    ///
+    pub fn dispids<S: AsRef<OsStr>>(&self, names: &[S]) -> Result<Vec<i32>> {
+        names.iter().map(|name| self.dispid(name)).collect()
+    }
+    pub fn clear_dispid_cache(&self) {
+        self.dispids.borrow_mut().clear();
+        self.putref_dispids.borrow_mut().clear();
+    }
+    pub fn responds_to<S: AsRef<OsStr>>(&self, method: S) -> bool {
+        self.dispid(method).is_ok()
     }
     fn get_type_info(&self) -> Result<ITypeInfo> {
         let typeinfo = unsafe { self.dispatch.GetTypeInfo(0, GetUserDefaultLCID()) };
@@ -106,6 +243,26 @@ impl OleData {
         let typeinfo = self.get_type_info()?;
         OleTypeData::try_from(typeinfo)
     }
+    /// The coclass of the object, as opposed to the dispatch interface
+    /// returned by [`OleData::ole_type`].
+    ///
+    /// Uses `IProvideClassInfo` when the object implements it, and otherwise
+    /// looks up the object's CLSID in the registry.
+    ///
+    pub fn ole_coclass(&self) -> Result<OleTypeData> {
+        coclass::ole_coclass(self)
+    }
+    /// The IID of the dispinterface through which the object raises its
+    /// events, or `None` if it has no default source interface.
+    ///
+    pub fn default_source_iid(&self) -> Result<Option<GUID>> {
+        coclass::default_source_iid(self)
+    }
+    /// The CLSID the object was created or connected with, if known.
+    ///
+    pub fn clsid(&self) -> Option<GUID> {
+        self.clsid
+    }
     pub fn ole_typelib(&self) -> Result<OleTypeLibData> {
         let typeinfo = self.get_type_info()?;
         OleTypeLibData::try_from(&typeinfo)
@@ -161,6 +318,7 @@ impl OleData {
         }
         Ok(ret_type_info.unwrap())
     }
+    #[track_caller]
     pub fn ole_query_interface<S: AsRef<OsStr>>(&self, str_iid: S) -> Result<OleData> {
         let iid = get_class_id(str_iid)?;
         let mut dispatch_interface = ptr::null_mut();
@@ -171,7 +329,7 @@ impl OleData {
         } else {
             let dispatch: IDispatch =
                 unsafe { <IDispatch as Interface>::from_raw(dispatch_interface as *mut _) };
-            Ok(OleData { dispatch })
+            Ok(OleData::from_dispatch(dispatch))
         }
     }
     pub fn ole_method_help<S: AsRef<OsStr>>(&self, cmdname: S) -> Result<OleMethodData> {
@@ -194,21 +352,50 @@ impl OleData {
         }
     }
 
-    pub fn invoke<S: AsRef<OsStr> + Copy>(
+    pub fn invoke<S: AsRef<OsStr>>(
         &self,
         name: S,
         dp: &mut DISPPARAMS,
         flags: DISPATCH_FLAGS,
     ) -> Result<VARIANT> {
-        let ids = self.get_ids_of_names(&[name])?;
+        let dispid = self.dispid(name)?;
+        self.invoke_dispid(dispid, dp, flags)
+    }
+
+    /// Invoke a member by DISPID, e.g. `DISPID_VALUE`, `DISPID_NEWENUM` or
+    /// `DISPID_EVALUATE`, without any name lookup.
+    ///
+    pub fn invoke_dispid(
+        &self,
+        dispid: i32,
+        dp: &mut DISPPARAMS,
+        flags: DISPATCH_FLAGS,
+    ) -> Result<VARIANT> {
+        ensure_apartment()?;
+        self.retrying(|| self.invoke_once(dispid, dp, flags))
+    }
 
+    /// Run `f` under the retry policy, unless a message filter is retrying.
+    pub(crate) fn retrying<T, F: FnMut() -> Result<T>>(&self, mut f: F) -> Result<T> {
+        match &self.retry {
+            Some(policy) if !message_filter_registered() => policy.run(f),
+            _ => f(),
+        }
+    }
+
+    fn invoke_once(
+        &self,
+        dispid: i32,
+        dp: &mut DISPPARAMS,
+        flags: DISPATCH_FLAGS,
+    ) -> Result<VARIANT> {
         let mut excep = EXCEPINFO::default();
         let mut arg_err = 0;
         let mut result = VARIANT::default();
 
         let res = unsafe {
             self.dispatch.Invoke(
-                ids[0],
+                dispid,
                 &GUID::zeroed(),
                 0x0800, /*LOCALE_SYSTEM_DEFAULT*/
                 flags,
@@ -222,7 +409,12 @@ impl OleData {
         match res {
             Ok(()) => Ok(result),
             Err(e) => Err(match e.code() {
-                DISP_E_EXCEPTION => Error::Exception(excep),
+                DISP_E_EXCEPTION => Error::Exception(Box::new(ExceptionInfo::capture(
+                    excep,
+                    dispid,
+                    self.member_name(dispid),
+                    unsafe { render_args(dp) },
+                ))),
                 DISP_E_TYPEMISMATCH => Error::IDispatchArgument {
                     error_type: ComArgumentErrorType::TypeMismatch,
                     arg_err,
@@ -236,6 +428,109 @@ impl OleData {
         }
     }
 
+    /// Name of the member with `dispid`, from the type information or else from
+    /// the DISPID cache.
+    fn member_name(&self, dispid: i32) -> Option<String> {
+        if let Ok(typeinfo) = unsafe { self.dispatch.GetTypeInfo(0, GetUserDefaultLCID()) } {
+            let mut name = BSTR::default();
+            let result = unsafe {
+                typeinfo.GetDocumentation(dispid, Some(&mut name), None, ptr::null_mut(), None)
+            };
+            if result.is_ok() {
+                return Some(name.to_string());
+            }
+        }
+        self.dispids
+            .borrow()
+            .iter()
+            .find(|(_, id)| **id == dispid)
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Invoke a member by DISPID with `args` given in declaration order.
+    ///
+    /// For `DISPATCH_PROPERTYPUT` and `DISPATCH_PROPERTYPUTREF` the last argument
+    /// is passed as the new value of the property.
+    ///
+    pub fn call_dispid(
+        &self,
+        dispid: i32,
+        args: Vec<VARIANT>,
+        flags: DISPATCH_FLAGS,
+    ) -> Result<VARIANT> {
+        let mut args: Vec<VARIANT> = args.into_iter().rev().collect();
+        self.invoke_reversed(dispid, &mut args, flags)
+    }
+
+    /// Invoke with `args` already in `DISPPARAMS` order, i.e. last argument first.
+    fn invoke_reversed(
+        &self,
+        dispid: i32,
+        args: &mut [VARIANT],
+        flags: DISPATCH_FLAGS,
+    ) -> Result<VARIANT> {
+        let mut named_arg = DISPID_PROPERTYPUT;
+        let mut dp = DISPPARAMS {
+            cArgs: args.len() as u32,
+            rgvarg: args.as_mut_ptr(),
+            ..Default::default()
+        };
+        if flags.0 & (DISPATCH_PROPERTYPUT.0 | DISPATCH_PROPERTYPUTREF.0) != 0 {
+            dp.cNamedArgs = 1;
+            dp.rgdispidNamedArgs = &mut named_arg;
+        }
+        self.invoke_dispid(dispid, &mut dp, flags)
+    }
+
+    /// Invoke a member described by an [`OleMethodData`], e.g. one returned by
+    /// [`OleData::ole_methods`]. The `DISPATCH_*` flags are derived from its `invkind`.
+    ///
+    pub fn invoke_method(&self, method: &OleMethodData, args: Vec<VARIANT>) -> Result<VARIANT> {
+        let flags = match method.invkind() {
+            INVOKE_PROPERTYGET => DISPATCH_PROPERTYGET,
+            INVOKE_PROPERTYPUT => DISPATCH_PROPERTYPUT,
+            INVOKE_PROPERTYPUTREF => DISPATCH_PROPERTYPUTREF,
+            _ => DISPATCH_METHOD,
+        };
+        self.call_dispid(method.dispid(), args, flags)
+    }
+
+    /// Call a method through the object's vtable with `DispCallFunc`, bypassing
+    /// `IDispatch::Invoke`.
+    ///
+    /// This works for dual interfaces, whose dispinterface members are mapped
+    /// to the interface half, and for plain `TKIND_INTERFACE` methods. `args`
+    /// are coerced to the parameter types of the `FUNCDESC`, and the
+    /// `[retval]` parameter is returned like the result of [`OleData::invoke_method`].
+    ///
+    /// To receive the value of another `[out]` or `[in, out]` parameter, pass
+    /// a `VT_BYREF` VARIANT pointing at storage of the parameter's type. Values
+    /// passed by value only go in; what the method writes back is discarded.
+    ///
+    pub fn invoke_vtbl(&self, method: &OleMethodData, args: Vec<VARIANT>) -> Result<VARIANT> {
+        dispcall::invoke_vtbl(self, method, args)
+    }
+
+    /// Call a method by name through the vtable, see [`OleData::invoke_vtbl`]
+    ///
+    pub fn call_vtbl(&self, name: &str, args: Vec<VARIANT>) -> Result<VARIANT> {
+        self.invoke_vtbl(&self.ole_method_help(name)?, args)
+    }
+
+    /// Iterate over a COM collection, like `WIN32OLE#each`.
+    ///
+    /// This invokes `DISPID_NEWENUM` and walks the returned `IEnumVARIANT`.
+    ///
+    pub fn each(&self) -> Result<OleEnum> {
+        let mut dp = DISPPARAMS::default();
+        let result = self.invoke_dispid(
+            DISPID_NEWENUM,
+            &mut dp,
+            DISPATCH_METHOD | DISPATCH_PROPERTYGET,
+        )?;
+        OleEnum::from_variant(&result)
+    }
+
     /// Get a property from a COM object
     ///
     pub fn get(&self, name: &str) -> Result<VARIANT> {
@@ -246,26 +541,236 @@ impl OleData {
     /// Set a property on a COM object
     ///
     pub fn put(&self, name: &str, value: &mut VARIANT) -> Result<()> {
-        let mut dp = DISPPARAMS {
-            cArgs: 1,
-            rgvarg: value,
-            cNamedArgs: 1,
-            ..Default::default()
+        let dispid = self.dispid(name)?;
+        self.invoke_reversed(dispid, slice::from_mut(value), DISPATCH_PROPERTYPUT)?;
+        Ok(())
+    }
+
+    /// Assign an object reference to a property on a COM object (`Set obj.Prop = value`)
+    ///
+    pub fn putref(&self, name: &str, value: &mut VARIANT) -> Result<()> {
+        let dispid = self.dispid(name)?;
+        self.invoke_reversed(dispid, slice::from_mut(value), DISPATCH_PROPERTYPUTREF)?;
+        Ok(())
+    }
+
+    /// Set a property, choosing between [`OleData::put`] and [`OleData::putref`]
+    ///
+    /// Object values are assigned by reference when the type information lists
+    /// a `propputref` accessor for the property; everything else is assigned
+    /// by value.
+    ///
+    pub fn set(&self, name: &str, value: &mut VARIANT) -> Result<()> {
+        if self.supports_putref(name, value) {
+            self.putref(name, value)
+        } else {
+            self.put(name, value)
+        }
+    }
+
+    fn supports_putref(&self, name: &str, value: &VARIANT) -> bool {
+        let mut vt = unsafe { value.Anonymous.Anonymous.vt.0 };
+        if vt & VT_BYREF.0 != 0 {
+            vt &= !VT_BYREF.0;
+        }
+        if vt != VT_DISPATCH.0 && vt != VT_UNKNOWN.0 {
+            return false;
+        }
+        let Ok(dispid) = self.dispid(name) else {
+            return false;
         };
-        let mut id = DISPID_PROPERTYPUT;
-        dp.rgdispidNamedArgs = &mut id as *mut _;
-        self.invoke(name, &mut dp, DISPATCH_PROPERTYPUT)?;
+        if let Some(supported) = self.putref_dispids.borrow().get(&dispid) {
+            return *supported;
+        }
+        // Objects without type information are assigned by value.
+        let supported = self.ole_put_methods().is_ok_and(|put_methods| {
+            put_methods.iter().any(|method| {
+                method.dispid() == dispid && method.invkind().0 & INVOKE_PROPERTYPUTREF.0 != 0
+            })
+        });
+        self.putref_dispids.borrow_mut().insert(dispid, supported);
+        supported
+    }
+
+    /// Get a parameterized property, e.g. `Cells(1, 2)` or `Worksheets("Sheet1")`
+    ///
+    /// When the property itself takes no arguments, the arguments are applied to
+    /// the default member of its value, as VB does for `Worksheets("Sheet1")`.
+    ///
+    pub fn get_with_args(&self, name: &str, args: Vec<VARIANT>) -> Result<VARIANT> {
+        if args.is_empty() {
+            return self.get(name);
+        }
+        let dispid = self.dispid(name)?;
+        let mut args: Vec<VARIANT> = args.into_iter().rev().collect();
+        let flags = DISPATCH_METHOD | DISPATCH_PROPERTYGET;
+        match self.invoke_reversed(dispid, &mut args, flags) {
+            Err(Error::Windows(error)) if error.code() == DISP_E_BADPARAMCOUNT => {
+                let value = self.invoke_reversed(dispid, &mut [], DISPATCH_PROPERTYGET)?;
+                let Some(object) = OleValue::try_from(value)?.into_object() else {
+                    return Err(Error::Windows(error));
+                };
+                object.invoke_reversed(DISPID_VALUE, &mut args, flags)
+            }
+            result => result,
+        }
+    }
+
+    /// Set a parameterized property, e.g. `Range.Value(xlRangeValueDefault) = value`
+    ///
+    pub fn put_with_args(&self, name: &str, args: Vec<VARIANT>, value: VARIANT) -> Result<()> {
+        let mut args = args;
+        args.push(value);
+        self.call_dispid(self.dispid(name)?, args, DISPATCH_PROPERTYPUT)?;
+        Ok(())
+    }
+
+    /// Assign an object reference to a parameterized property (`Set obj.Prop(args) = value`)
+    ///
+    pub fn putref_with_args(&self, name: &str, args: Vec<VARIANT>, value: VARIANT) -> Result<()> {
+        let mut args = args;
+        args.push(value);
+        self.call_dispid(self.dispid(name)?, args, DISPATCH_PROPERTYPUTREF)?;
+        Ok(())
+    }
+
+    /// Get the default member (`DISPID_VALUE`), as in VB's `obj(args)`
+    ///
+    pub fn default_value(&self, args: Vec<VARIANT>) -> Result<VARIANT> {
+        self.call_dispid(DISPID_VALUE, args, DISPATCH_METHOD | DISPATCH_PROPERTYGET)
+    }
+
+    /// Set the default member (`DISPID_VALUE`), as in VB's `obj(args) = value`
+    ///
+    pub fn put_default_value(&self, args: Vec<VARIANT>, value: VARIANT) -> Result<()> {
+        let mut args = args;
+        args.push(value);
+        self.call_dispid(DISPID_VALUE, args, DISPATCH_PROPERTYPUT)?;
         Ok(())
     }
 
+    /// Register this object in the Global Interface Table so that it can be
+    /// used from another thread, see [`AgileOleData`]
+    ///
+    pub fn to_agile(&self) -> Result<AgileOleData> {
+        AgileOleData::new(self)
+    }
+
+    /// Access the object through `IDispatchEx`, for script objects whose
+    /// members are only known at run time, see [`DispatchEx`]
+    ///
+    pub fn to_dispatch_ex(&self) -> Result<DispatchEx> {
+        DispatchEx::new(self)
+    }
+
+    /// Evaluate a dotted property path such as
+    /// `ActiveWorkbook.Sheets(1).Range("A1").Value`
+    ///
+    /// Each segment is a member name, optionally followed by string, number or
+    /// boolean arguments. Every intermediate segment must return an object; a
+    /// failure reports the segment together with the underlying error.
+    ///
+    #[track_caller]
+    pub fn navigate(&self, path: &str) -> Result<OleValue> {
+        navigate::navigate(self, path)
+    }
+
     /// Call a method on a COM object
     ///
     pub fn call(&self, name: &str, args: Vec<VARIANT>) -> Result<VARIANT> {
-        let mut dp = DISPPARAMS::default();
-        let args: Vec<VARIANT> = args.into_iter().rev().collect();
-        dp.cArgs = args.len() as u32;
-        dp.rgvarg = args.as_ptr() as *mut _;
-        self.invoke(name, &mut dp, DISPATCH_METHOD)
+        self.call_dispid(self.dispid(name)?, args, DISPATCH_METHOD)
+    }
+
+    /// Get a property as an [`OleValue`], with objects wrapped in [`OleData`]
+    ///
+    #[track_caller]
+    pub fn get_value(&self, name: &str) -> Result<OleValue> {
+        OleValue::try_from(self.get(name)?)
+    }
+
+    /// Call a method and return its result as an [`OleValue`]
+    ///
+    #[track_caller]
+    pub fn call_value(&self, name: &str, args: Vec<VARIANT>) -> Result<OleValue> {
+        OleValue::try_from(self.call(name, args)?)
+    }
+
+    /// Get a property that holds an object
+    ///
+    /// Returns `Ok(None)` when the property is `Nothing`, `Empty` or `Null`, and
+    /// an error when it holds a value that is not an object.
+    ///
+    #[track_caller]
+    pub fn get_object(&self, name: &str) -> Result<Option<OleData>> {
+        expect_object(name, self.get_value(name)?)
+    }
+
+    /// Call a method that returns an object, see [`OleData::get_object`]
+    ///
+    #[track_caller]
+    pub fn call_object(&self, name: &str, args: Vec<VARIANT>) -> Result<Option<OleData>> {
+        expect_object(name, self.call_value(name, args)?)
+    }
+}
+
+/// Render the arguments of `dp` in declaration order, for error reports.
+pub(crate) unsafe fn render_args(dp: &DISPPARAMS) -> Vec<String> {
+    if dp.rgvarg.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(dp.rgvarg, dp.cArgs as usize)
+        .iter()
+        .rev()
+        .map(|arg| match OleValue::try_from(arg) {
+            Ok(OleValue::String(s)) => format!("{s:?}"),
+            Ok(value) => value.to_string(),
+            Err(_) => format!("<VARIANT type {}>", arg.Anonymous.Anonymous.vt.0),
+        })
+        .collect()
+}
+
+fn expect_object(name: &str, value: OleValue) -> Result<Option<OleData>> {
+    match value {
+        OleValue::Object(object) => Ok(Some(object)),
+        OleValue::Nothing | OleValue::Empty | OleValue::Null => Ok(None),
+        value => Err(Error::Custom(format!(
+            "`{name}` returned {value} instead of an object"
+        ))),
+    }
+}
+
+impl Clone for OleData {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        OleData {
+            _tracker: Tracker::new(self.dispatch.as_raw() as usize),
+            dispatch: self.dispatch.clone(),
+            dispids: self.dispids.clone(),
+            putref_dispids: self.putref_dispids.clone(),
+            retry: self.retry.clone(),
+            clsid: self.clsid,
+        }
+    }
+}
+
+impl PartialEq for OleData {
+    fn eq(&self, other: &Self) -> bool {
+        self.dispatch == other.dispatch
+    }
+}
+
+impl fmt::Debug for OleData {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("OleData")
+            .field("dispatch", &self.dispatch)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<IDispatch> for OleData {
+    #[track_caller]
+    fn from(dispatch: IDispatch) -> Self {
+        OleData::from_dispatch(dispatch)
     }
 }
 
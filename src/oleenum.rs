@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use windows::{
+    core::Interface,
+    Win32::{
+        Foundation::{DISP_E_TYPEMISMATCH, S_FALSE},
+        System::{
+            Ole::IEnumVARIANT,
+            Variant::{VariantClear, VARIANT, VT_BYREF, VT_DISPATCH, VT_UNKNOWN},
+        },
+    },
+};
+
+use crate::{
+    error::{Error, OleError, Result},
+    olevalue::OleValue,
+};
+
+const DEFAULT_BATCH_SIZE: u32 = 16;
+
+/// Iterator over a COM collection through its `IEnumVARIANT` enumerator.
+///
+/// Elements are fetched from the enumerator `batch_size` at a time and yielded
+/// as owned [`OleValue`]s; objects come back as [`OleValue::Object`].
+pub struct OleEnum {
+    enum_variant: IEnumVARIANT,
+    batch_size: u32,
+    buffer: VecDeque<Result<OleValue>>,
+    exhausted: bool,
+}
+
+impl OleEnum {
+    pub fn new(enum_variant: IEnumVARIANT) -> OleEnum {
+        OleEnum {
+            enum_variant,
+            batch_size: DEFAULT_BATCH_SIZE,
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+    /// Create an enumerator from the result of invoking `DISPID_NEWENUM`.
+    ///
+    pub fn from_variant(variant: &VARIANT) -> Result<OleEnum> {
+        let vt = unsafe { variant.Anonymous.Anonymous.vt };
+        let data = unsafe { &variant.Anonymous.Anonymous.Anonymous };
+        let enum_variant: Option<windows::core::Result<IEnumVARIANT>> = unsafe {
+            match vt {
+                VT_UNKNOWN => data.punkVal.as_ref().map(|unknown| unknown.cast()),
+                VT_DISPATCH => data.pdispVal.as_ref().map(|dispatch| dispatch.cast()),
+                _ if vt.0 == VT_BYREF.0 | VT_UNKNOWN.0 => data
+                    .ppunkVal
+                    .as_ref()
+                    .and_then(Option::as_ref)
+                    .map(|unknown| unknown.cast()),
+                _ if vt.0 == VT_BYREF.0 | VT_DISPATCH.0 => data
+                    .ppdispVal
+                    .as_ref()
+                    .and_then(Option::as_ref)
+                    .map(|dispatch| dispatch.cast()),
+                _ => {
+                    return Err(OleError::runtime(
+                        DISP_E_TYPEMISMATCH,
+                        format!(
+                            "DISPID_NEWENUM returned a VARIANT of type {}, not an object",
+                            vt.0
+                        ),
+                    )
+                    .into())
+                }
+            }
+        };
+        let Some(enum_variant) = enum_variant else {
+            return Err(Error::Generic("DISPID_NEWENUM returned a null enumerator"));
+        };
+        Ok(OleEnum::new(enum_variant?))
+    }
+    pub fn with_batch_size(mut self, batch_size: u32) -> OleEnum {
+        self.set_batch_size(batch_size);
+        self
+    }
+    pub fn set_batch_size(&mut self, batch_size: u32) {
+        self.batch_size = batch_size.max(1);
+    }
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+    /// Skip `count` elements, using `IEnumVARIANT::Skip` for any that have not
+    /// been fetched yet.
+    ///
+    pub fn skip_items(&mut self, count: u32) -> Result<()> {
+        let buffered = (count as usize).min(self.buffer.len());
+        self.buffer.drain(..buffered);
+        let remaining = count - buffered as u32;
+        if remaining > 0 && !self.exhausted {
+            let hresult = unsafe { self.enum_variant.Skip(remaining) };
+            if hresult == S_FALSE {
+                self.exhausted = true;
+            } else {
+                hresult.ok()?;
+            }
+        }
+        Ok(())
+    }
+    /// Restart the enumeration from the first element.
+    ///
+    pub fn reset(&mut self) -> Result<()> {
+        unsafe { self.enum_variant.Reset()? };
+        self.buffer.clear();
+        self.exhausted = false;
+        Ok(())
+    }
+    /// Create an independent enumerator positioned at the same element.
+    ///
+    pub fn try_clone(&self) -> Result<OleEnum> {
+        let enum_variant = unsafe { self.enum_variant.Clone()? };
+        let buffer = self
+            .buffer
+            .iter()
+            .map(|value| match value {
+                Ok(value) => Ok(value.clone()),
+                Err(error) => Err(Error::Custom(error.to_string())),
+            })
+            .collect();
+        Ok(OleEnum {
+            enum_variant,
+            batch_size: self.batch_size,
+            buffer,
+            exhausted: self.exhausted,
+        })
+    }
+    #[track_caller]
+    fn fetch(&mut self) -> Result<()> {
+        let mut variants: Vec<VARIANT> = (0..self.batch_size).map(|_| VARIANT::default()).collect();
+        let mut fetched = 0;
+        let hresult = unsafe { self.enum_variant.Next(&mut variants, &mut fetched) };
+        hresult.ok()?;
+        if hresult == S_FALSE || fetched < self.batch_size {
+            self.exhausted = true;
+        }
+        for variant in variants.iter_mut().take(fetched as usize) {
+            self.buffer.push_back(OleValue::try_from(&*variant));
+            unsafe { VariantClear(variant)? };
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for OleEnum {
+    type Item = Result<OleValue>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.exhausted {
+            if let Err(error) = self.fetch() {
+                self.exhausted = true;
+                return Some(Err(error));
+            }
+        }
+        self.buffer.pop_front()
+    }
+}
@@ -1,4 +1,10 @@
 use crate::{
+    custdata::{format_custom_attributes, func_custom_data, CustomData},
+    dllentry::{dll_entry, invoke_dll_entry, DllEntry},
+    documentation::{
+        typeinfo_documentation, typeinfo_localized_documentation, Documentation, Documented,
+        LocalizedDocumentation,
+    },
     error::Result,
     oleparamdata::OleParamData,
     types::{Methods, ReferencedTypes},
@@ -9,19 +15,107 @@ use crate::{
     OleTypeData,
 };
 use std::{
-    ffi::OsStr, ops::Deref, ptr::{self, NonNull}
+    ffi::OsStr,
+    mem,
+    ops::{BitAnd, BitOr, BitOrAssign, Deref},
+    ptr::NonNull,
 };
 use windows::{
-    core::{BSTR, PCWSTR},
+    core::{HRESULT, PCWSTR},
     Win32::System::{
         Com::{
-            ITypeInfo, FUNCDESC, FUNCKIND, INVOKEKIND, INVOKE_FUNC, INVOKE_PROPERTYGET,
-            INVOKE_PROPERTYPUT, INVOKE_PROPERTYPUTREF, TKIND_COCLASS, TYPEATTR, TYPEDESC,
+            ITypeInfo, CALLCONV, CC_CDECL, CC_FASTCALL, CC_FPFASTCALL, CC_MACPASCAL, CC_MPWCDECL,
+            CC_MPWPASCAL, CC_PASCAL, CC_STDCALL, CC_SYSCALL, FUNCDESC, FUNCKIND, FUNC_PUREVIRTUAL,
+            FUNC_VIRTUAL, INVOKEKIND, INVOKE_FUNC, INVOKE_PROPERTYGET, INVOKE_PROPERTYPUT,
+            INVOKE_PROPERTYPUTREF, TKIND_COCLASS, TYPEATTR, TYPEDESC,
         },
-        Variant::VARENUM,
+        Variant::{VARENUM, VARIANT},
     },
 };
 
+/// The `wFuncFlags` of a `FUNCDESC`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FuncFlags(pub u16);
+
+impl FuncFlags {
+    pub const RESTRICTED: FuncFlags = FuncFlags(0x1);
+    pub const SOURCE: FuncFlags = FuncFlags(0x2);
+    pub const BINDABLE: FuncFlags = FuncFlags(0x4);
+    pub const REQUESTEDIT: FuncFlags = FuncFlags(0x8);
+    pub const DISPLAYBIND: FuncFlags = FuncFlags(0x10);
+    pub const DEFAULTBIND: FuncFlags = FuncFlags(0x20);
+    pub const HIDDEN: FuncFlags = FuncFlags(0x40);
+    pub const USESGETLASTERROR: FuncFlags = FuncFlags(0x80);
+    pub const DEFAULTCOLLELEM: FuncFlags = FuncFlags(0x100);
+    pub const UIDEFAULT: FuncFlags = FuncFlags(0x200);
+    pub const NONBROWSABLE: FuncFlags = FuncFlags(0x400);
+    pub const REPLACEABLE: FuncFlags = FuncFlags(0x800);
+    pub const IMMEDIATEBIND: FuncFlags = FuncFlags(0x1000);
+
+    const NAMES: [(FuncFlags, &'static str); 13] = [
+        (FuncFlags::RESTRICTED, "restricted"),
+        (FuncFlags::SOURCE, "source"),
+        (FuncFlags::BINDABLE, "bindable"),
+        (FuncFlags::REQUESTEDIT, "requestedit"),
+        (FuncFlags::DISPLAYBIND, "displaybind"),
+        (FuncFlags::DEFAULTBIND, "defaultbind"),
+        (FuncFlags::HIDDEN, "hidden"),
+        (FuncFlags::USESGETLASTERROR, "usesgetlasterror"),
+        (FuncFlags::DEFAULTCOLLELEM, "defaultcollelem"),
+        (FuncFlags::UIDEFAULT, "uidefault"),
+        (FuncFlags::NONBROWSABLE, "nonbrowsable"),
+        (FuncFlags::REPLACEABLE, "replaceable"),
+        (FuncFlags::IMMEDIATEBIND, "immediatebind"),
+    ];
+
+    pub const fn empty() -> FuncFlags {
+        FuncFlags(0)
+    }
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    pub const fn contains(&self, other: FuncFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+    pub const fn intersects(&self, other: FuncFlags) -> bool {
+        self.0 & other.0 != 0
+    }
+    /// The IDL attribute names of the flags that are set, e.g. `["hidden", "source"]`.
+    ///
+    pub fn names(&self) -> Vec<&'static str> {
+        FuncFlags::NAMES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+impl BitOr for FuncFlags {
+    type Output = FuncFlags;
+
+    fn bitor(self, rhs: FuncFlags) -> FuncFlags {
+        FuncFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for FuncFlags {
+    fn bitor_assign(&mut self, rhs: FuncFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for FuncFlags {
+    type Output = FuncFlags;
+
+    fn bitand(self, rhs: FuncFlags) -> FuncFlags {
+        FuncFlags(self.0 & rhs.0)
+    }
+}
+
 #[derive(Debug)]
 pub struct OleMethodData {
     owner_typeinfo: Option<ITypeInfo>,
@@ -97,57 +191,6 @@ impl OleMethodData {
 
         Ok(None)
     }
-    fn docinfo(
-        &self,
-        name: Option<*mut BSTR>,
-        helpstr: Option<*mut BSTR>,
-        helpcontext: *mut u32,
-        helpfile: Option<*mut BSTR>,
-    ) -> Result<()> {
-        unsafe {
-            self.typeinfo.GetDocumentation(
-                self.func_desc.as_ref().memid,
-                name,
-                helpstr,
-                helpcontext,
-                helpfile,
-            )?
-        };
-        Ok(())
-    }
-    pub fn get_documentation(&self) -> Result<(String, String, u32, String)> {
-        let mut strname = BSTR::default();
-        let mut strdocstring = BSTR::default();
-        let mut whelpcontext = 0;
-        let mut strhelpfile = BSTR::default();
-        self.docinfo(
-            Some(&mut strname),
-            Some(&mut strdocstring),
-            &mut whelpcontext,
-            Some(&mut strhelpfile),
-        )?;
-        Ok((
-            String::try_from(strname)?,
-            String::try_from(strdocstring)?,
-            whelpcontext,
-            String::try_from(strhelpfile)?,
-        ))
-    }
-    pub fn helpstring(&self) -> Result<String> {
-        let mut helpstring = BSTR::default();
-        self.docinfo(None, Some(&mut helpstring), ptr::null_mut(), None)?;
-        Ok(String::try_from(helpstring)?)
-    }
-    pub fn helpfile(&self) -> Result<String> {
-        let mut helpfile = BSTR::default();
-        self.docinfo(None, None, ptr::null_mut(), Some(&mut helpfile))?;
-        Ok(String::try_from(helpfile)?)
-    }
-    pub fn helpcontext(&self) -> Result<u32> {
-        let mut helpcontext = 0;
-        self.docinfo(None, None, &mut helpcontext, None)?;
-        Ok(helpcontext)
-    }
     pub fn dispid(&self) -> i32 {
         unsafe { self.func_desc.as_ref().memid }
     }
@@ -255,11 +298,58 @@ impl OleMethodData {
     pub fn offset_vtbl(&self) -> Result<i16> {
         Ok(unsafe { self.func_desc.as_ref().oVft })
     }
+    /// The vtable slot of the function, or `None` for functions that are only
+    /// reachable through `IDispatch::Invoke` or are not virtual.
+    ///
+    pub fn vtable_slot(&self) -> Option<usize> {
+        match self.funckind() {
+            FUNC_VIRTUAL | FUNC_PUREVIRTUAL => {
+                Some(self.desc().oVft as usize / mem::size_of::<usize>())
+            }
+            _ => None,
+        }
+    }
+    pub fn callconv(&self) -> CALLCONV {
+        unsafe { self.func_desc.as_ref().callconv }
+    }
+    pub fn calling_convention(&self) -> &'static str {
+        match self.callconv() {
+            CC_FASTCALL => "FASTCALL",
+            CC_CDECL => "CDECL",
+            CC_PASCAL => "PASCAL",
+            CC_MACPASCAL => "MACPASCAL",
+            CC_STDCALL => "STDCALL",
+            CC_FPFASTCALL => "FPFASTCALL",
+            CC_SYSCALL => "SYSCALL",
+            CC_MPWCDECL => "MPWCDECL",
+            CC_MPWPASCAL => "MPWPASCAL",
+            _ => "UNKNOWN",
+        }
+    }
+    pub fn func_flags(&self) -> FuncFlags {
+        FuncFlags(unsafe { self.func_desc.as_ref().wFuncFlags.0 })
+    }
+    /// Whether the method should be shown in an API browser, i.e. it is neither
+    /// `[hidden]` nor `[restricted]`.
+    ///
+    pub fn visible(&self) -> bool {
+        !self
+            .func_flags()
+            .intersects(FuncFlags::HIDDEN | FuncFlags::RESTRICTED)
+    }
+    /// The status codes the function is declared to return.
+    ///
+    pub fn scodes(&self) -> Vec<HRESULT> {
+        let desc = self.desc();
+        if desc.lprgscode.is_null() || desc.cScodes <= 0 {
+            return vec![];
+        }
+        let scodes = unsafe { std::slice::from_raw_parts(desc.lprgscode, desc.cScodes as usize) };
+        scodes.iter().map(|scode| HRESULT(*scode)).collect()
+    }
     pub fn event_interface(&self) -> Result<Option<String>> {
         if self.is_event() {
-            let mut name = BSTR::default();
-            self.docinfo(Some(&mut name), None, ptr::null_mut(), None)?;
-            return Ok(Some(name.to_string()));
+            return Ok(Some(self.documentation()?.name));
         }
         Ok(None)
     }
@@ -275,6 +365,41 @@ impl OleMethodData {
     pub fn get_ref_type_info(&self, href: u32) -> Result<ITypeInfo> {
         Ok(unsafe { self.typeinfo.GetRefTypeInfo(href)? })
     }
+    pub fn custom_data(&self) -> Result<CustomData> {
+        func_custom_data(&self.typeinfo, self.index)
+    }
+    /// The IDL attributes of the method, e.g. `["id(0x00000000)", "propget", "hidden"]`.
+    ///
+    pub fn idl_attributes(&self) -> Result<Vec<String>> {
+        let mut attributes = vec![format!("id({:#010x})", self.dispid())];
+        let invkind = self.invkind();
+        if invkind.0 & INVOKE_PROPERTYGET.0 != 0 {
+            attributes.push("propget".into());
+        }
+        if invkind.0 & INVOKE_PROPERTYPUT.0 != 0 {
+            attributes.push("propput".into());
+        }
+        if invkind.0 & INVOKE_PROPERTYPUTREF.0 != 0 {
+            attributes.push("propputref".into());
+        }
+        attributes.extend(self.func_flags().names().into_iter().map(String::from));
+        attributes.extend(format_custom_attributes(&self.custom_data()?));
+        Ok(attributes)
+    }
+    /// The `[dllname]` and `[entry]` of a function declared in a type library
+    /// `module`, or `None` for any other function.
+    ///
+    pub fn dll_entry(&self) -> Result<Option<DllEntry>> {
+        dll_entry(self)
+    }
+    /// Call a `module` function in its DLL, see [`OleMethodData::dll_entry`].
+    ///
+    /// `args` are given in declaration order and coerced to the parameter
+    /// types; the `[retval]` parameter, or else the return value, is returned.
+    ///
+    pub fn invoke_dll(&self, args: Vec<VARIANT>) -> Result<VARIANT> {
+        invoke_dll_entry(self, args)
+    }
 }
 
 impl Drop for OleMethodData {
@@ -295,6 +420,15 @@ impl TypeRef for OleMethodData {
 
 impl ValueDescription for OleMethodData {}
 
+impl Documented for OleMethodData {
+    fn documentation(&self) -> Result<Documentation> {
+        typeinfo_documentation(&self.typeinfo, self.dispid())
+    }
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation> {
+        typeinfo_localized_documentation(&self.typeinfo, self.dispid(), lcid)
+    }
+}
+
 pub(crate) fn ole_methods_from_typeinfo(
     typeinfo: ITypeInfo,
     mask: i32,
@@ -315,6 +449,18 @@ pub(crate) fn ole_methods_from_typeinfo(
     Ok(methods)
 }
 
+/// Methods declared by `typeinfo` itself, without those of its implemented types.
+pub(crate) fn ole_declared_methods(typeinfo: &ITypeInfo) -> Result<Vec<OleMethodData>> {
+    let mut methods = vec![];
+    ole_methods_sub(
+        None,
+        typeinfo,
+        &mut methods,
+        INVOKE_FUNC.0 | INVOKE_PROPERTYGET.0 | INVOKE_PROPERTYPUT.0 | INVOKE_PROPERTYPUTREF.0,
+    )?;
+    Ok(methods)
+}
+
 fn ole_methods_sub(
     owner_typeinfo: Option<&ITypeInfo>,
     typeinfo: &ITypeInfo,
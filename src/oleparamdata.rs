@@ -6,6 +6,11 @@ use windows::Win32::System::{
 };
 
 use crate::{
+    custdata::{format_custom_attributes, param_custom_data, CustomData},
+    documentation::{
+        typeinfo_documentation, typeinfo_localized_documentation, Documentation, Documented,
+        LocalizedDocumentation,
+    },
     error::{Error, Result},
     util::ole::{TypeRef, ValueDescription},
     OleMethodData,
@@ -102,6 +107,26 @@ impl OleParamData {
         unsafe { self.typeinfo.ReleaseFuncDesc(funcdesc) };
         defval
     }*/
+    pub fn custom_data(&self) -> Result<CustomData> {
+        param_custom_data(&self.typeinfo, self.method_index, self.index)
+    }
+    /// The IDL attributes of the parameter, e.g. `["out", "retval"]`.
+    ///
+    pub fn idl_attributes(&self) -> Result<Vec<String>> {
+        let flags = [
+            (self.input(), "in"),
+            (self.output(), "out"),
+            (self.optional(), "optional"),
+            (self.retval(), "retval"),
+        ];
+        let mut attributes: Vec<String> = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, name)| (*name).into())
+            .collect();
+        attributes.extend(format_custom_attributes(&self.custom_data()?));
+        Ok(attributes)
+    }
     pub fn elem_desc(&self) -> &ELEMDESC {
         unsafe {
             &*self
@@ -135,6 +160,28 @@ impl TypeRef for OleParamData {
 
 impl ValueDescription for OleParamData {}
 
+/// Parameters have no documentation entries of their own, so the help file and
+/// context of the owning method are reported alongside the parameter name.
+impl Documented for OleParamData {
+    fn documentation(&self) -> Result<Documentation> {
+        let memid = unsafe { self.func_desc.as_ref().memid };
+        let method_documentation = typeinfo_documentation(&self.typeinfo, memid)?;
+        Ok(Documentation {
+            name: self.name.clone(),
+            doc_string: String::new(),
+            ..method_documentation
+        })
+    }
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation> {
+        let memid = unsafe { self.func_desc.as_ref().memid };
+        let method_documentation = typeinfo_localized_documentation(&self.typeinfo, memid, lcid)?;
+        Ok(LocalizedDocumentation {
+            help_string: String::new(),
+            ..method_documentation
+        })
+    }
+}
+
 fn oleparam_ole_param_from_index(
     typeinfo: &ITypeInfo,
     method_index: u32,
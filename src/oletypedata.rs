@@ -1,12 +1,19 @@
 use crate::{
+    apartment::ensure_apartment,
+    custdata::{format_custom_attributes, impl_type_custom_data, type_custom_data, CustomData},
+    documentation::{
+        typeinfo_documentation, typeinfo_localized_documentation, Documentation, Documented,
+        LocalizedDocumentation,
+    },
     error::{Error, OleError, Result},
+    inheritance::{flattened_members, inheritance_chain, InheritedMember},
     olemethoddata::ole_methods_from_typeinfo,
     oletypelibdata::typelib_file,
     olevariabledata::OleVariableData,
     types::{OleClassNames, ReferencedTypes, TypeInfos, Variables},
     util::{
         conv::ToWide,
-        ole::{ole_docinfo, ole_initialized, TypeRef, ValueDescription},
+        ole::{TypeRef, ValueDescription},
     },
     OleMethodData,
 };
@@ -35,7 +42,7 @@ pub struct OleTypeData {
 
 impl OleTypeData {
     pub fn new<S: AsRef<OsStr>>(typelib: S, oleclass: S) -> Result<OleTypeData> {
-        ole_initialized();
+        ensure_apartment()?;
         let file = typelib_file(&typelib)?;
         let file_vec = file.to_wide_null();
         let typelib_iface =
@@ -63,52 +70,6 @@ impl OleTypeData {
     pub fn attribs(&self) -> &TYPEATTR {
         unsafe { self.type_attr.as_ref() }
     }
-    pub fn get_documentation(&self) -> Result<(String, String, u32, String)> {
-        let mut strname = BSTR::default();
-        let mut strdocstring = BSTR::default();
-        let mut whelpcontext = 0;
-        let mut strhelpfile = BSTR::default();
-        ole_docinfo(
-            &self.typeinfo,
-            Some(&mut strname),
-            Some(&mut strdocstring),
-            &mut whelpcontext,
-            Some(&mut strhelpfile),
-        )?;
-        Ok((
-            String::try_from(strname)?,
-            String::try_from(strdocstring)?,
-            whelpcontext,
-            String::try_from(strhelpfile)?,
-        ))
-    }
-    pub fn helpstring(&self) -> Result<String> {
-        let mut helpstring = BSTR::default();
-        ole_docinfo(
-            &self.typeinfo,
-            None,
-            Some(&mut helpstring),
-            ptr::null_mut(),
-            None,
-        )?;
-        Ok(String::try_from(helpstring)?)
-    }
-    pub fn helpfile(&self) -> Result<String> {
-        let mut helpfile = BSTR::default();
-        ole_docinfo(
-            &self.typeinfo,
-            None,
-            None,
-            ptr::null_mut(),
-            Some(&mut helpfile),
-        )?;
-        Ok(String::try_from(helpfile)?)
-    }
-    pub fn helpcontext(&self) -> Result<u32> {
-        let mut helpcontext = 0;
-        ole_docinfo(&self.typeinfo, None, None, &mut helpcontext, None)?;
-        Ok(helpcontext)
-    }
     pub fn major_version(&self) -> u16 {
         unsafe { self.type_attr.as_ref().wMajorVerNum }
     }
@@ -176,6 +137,18 @@ impl OleTypeData {
 
         Ok(types)
     }
+    /// The interface itself followed by each of its base interfaces, e.g.
+    /// `_Workbook`, `IDispatch`, `IUnknown`.
+    ///
+    pub fn inheritance_chain(&self) -> Result<Vec<OleTypeData>> {
+        inheritance_chain(self)
+    }
+    /// All members reachable through the interface, including inherited ones,
+    /// with the interface that declares each of them.
+    ///
+    pub fn flattened_members(&self) -> Result<Vec<InheritedMember>> {
+        flattened_members(self)
+    }
     pub fn implemented_ole_types(&self) -> Result<Vec<OleTypeData>> {
         self.ole_type_impl_ole_types(IMPLTYPEFLAGS(0))
     }
@@ -210,6 +183,22 @@ impl OleTypeData {
     pub fn num_variables(&self) -> u16 {
         unsafe { self.type_attr.as_ref().cVars }
     }
+    pub fn custom_data(&self) -> Result<CustomData> {
+        type_custom_data(&self.typeinfo)
+    }
+    pub fn impl_type_custom_data(&self, index: u32) -> Result<CustomData> {
+        impl_type_custom_data(&self.typeinfo, index)
+    }
+    /// The IDL attributes of the type, e.g. `["uuid(…)", "version(1.0)"]`.
+    ///
+    pub fn idl_attributes(&self) -> Result<Vec<String>> {
+        let mut attributes = vec![
+            format!("uuid({:?})", self.guid()),
+            format!("version({}.{})", self.major_version(), self.minor_version()),
+        ];
+        attributes.extend(format_custom_attributes(&self.custom_data()?));
+        Ok(attributes)
+    }
 }
 
 impl Drop for OleTypeData {
@@ -230,6 +219,15 @@ impl TypeRef for OleTypeData {
 
 impl ValueDescription for OleTypeData {}
 
+impl Documented for OleTypeData {
+    fn documentation(&self) -> Result<Documentation> {
+        typeinfo_documentation(&self.typeinfo, -1)
+    }
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation> {
+        typeinfo_localized_documentation(&self.typeinfo, -1, lcid)
+    }
+}
+
 impl TryFrom<ITypeInfo> for OleTypeData {
     type Error = Error;
 
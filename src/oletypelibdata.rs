@@ -6,6 +6,12 @@ use std::{
 };
 
 use crate::{
+    custdata::{format_custom_attributes, typelib_custom_data, CustomData},
+    dependencies::{typelib_dependencies, TypeLibDependencies},
+    documentation::{
+        typelib_documentation, typelib_localized_documentation, Documentation, Documented,
+        LocalizedDocumentation,
+    },
     error::{Error, OleError, Result},
     types::{OleClassNames, TypeInfos},
     util::{
@@ -267,6 +273,34 @@ impl OleTypeLibData {
     pub fn num_type_entries(&self) -> u32 {
         unsafe { self.typelib.GetTypeInfoCount() }
     }
+    pub fn custom_data(&self) -> Result<CustomData> {
+        typelib_custom_data(&self.typelib)
+    }
+    /// The IDL attributes of the library, e.g. `["uuid(…)", "version(1.0)"]`.
+    ///
+    pub fn idl_attributes(&self) -> Result<Vec<String>> {
+        let mut attributes = vec![
+            format!("uuid({:?})", self.guid()),
+            format!("version({}.{})", self.major_version(), self.minor_version()),
+        ];
+        attributes.extend(format_custom_attributes(&self.custom_data()?));
+        Ok(attributes)
+    }
+    /// The external type libraries this library references, grouped by GUID,
+    /// version and LCID, plus any references that could not be resolved.
+    ///
+    pub fn dependencies(&self) -> Result<TypeLibDependencies> {
+        typelib_dependencies(&self.typelib)
+    }
+}
+
+impl Documented for OleTypeLibData {
+    fn documentation(&self) -> Result<Documentation> {
+        typelib_documentation(&self.typelib, -1)
+    }
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation> {
+        typelib_localized_documentation(&self.typelib, -1, lcid)
+    }
 }
 
 impl TryFrom<&ITypeInfo> for OleTypeLibData {
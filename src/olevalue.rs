@@ -0,0 +1,289 @@
+use std::fmt;
+
+use windows::{
+    core::{IUnknown, Interface, BSTR},
+    Win32::System::{
+        Com::IDispatch,
+        Variant::{
+            VariantClear, VARENUM, VARIANT, VARIANT_0_0_0, VT_BOOL, VT_BSTR, VT_BYREF, VT_CY,
+            VT_DATE, VT_DISPATCH, VT_EMPTY, VT_ERROR, VT_I1, VT_I2, VT_I4, VT_I8, VT_INT, VT_NULL,
+            VT_R4, VT_R8, VT_UI1, VT_UI2, VT_UI4, VT_UI8, VT_UINT, VT_UNKNOWN, VT_VARIANT,
+        },
+    },
+};
+
+use crate::{
+    error::{Error, Result},
+    OleData,
+};
+
+/// An owned copy of the data held by a `VARIANT`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OleValue {
+    Empty,
+    Null,
+    Bool(bool),
+    I1(i8),
+    I2(i16),
+    I4(i32),
+    I8(i64),
+    UI1(u8),
+    UI2(u16),
+    UI4(u32),
+    UI8(u64),
+    R4(f32),
+    R8(f64),
+    /// Currency, scaled by 10,000.
+    Currency(i64),
+    /// OLE automation date: days since 30 December 1899.
+    Date(f64),
+    String(String),
+    Error(i32),
+    /// An automation object, returned as `VT_DISPATCH` or as a `VT_UNKNOWN`
+    /// that supports `IDispatch`.
+    Object(OleData),
+    /// A `VT_UNKNOWN` that does not support `IDispatch`.
+    Unknown(IUnknown),
+    /// A null object reference, VB's `Nothing`: a `VT_DISPATCH` or
+    /// `VT_UNKNOWN` holding a null pointer.
+    Nothing,
+}
+
+impl OleValue {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, OleValue::Empty)
+    }
+    pub fn is_null(&self) -> bool {
+        matches!(self, OleValue::Null)
+    }
+    pub fn is_nothing(&self) -> bool {
+        matches!(self, OleValue::Nothing)
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            OleValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            OleValue::I1(v) => Some(v as i64),
+            OleValue::I2(v) => Some(v as i64),
+            OleValue::I4(v) => Some(v as i64),
+            OleValue::I8(v) => Some(v),
+            OleValue::UI1(v) => Some(v as i64),
+            OleValue::UI2(v) => Some(v as i64),
+            OleValue::UI4(v) => Some(v as i64),
+            OleValue::UI8(v) => i64::try_from(v).ok(),
+            _ => None,
+        }
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            OleValue::R4(v) => Some(v as f64),
+            OleValue::R8(v) => Some(v),
+            OleValue::Date(v) => Some(v),
+            OleValue::Currency(v) => Some(v as f64 / 10_000.0),
+            _ => self.as_i64().map(|v| v as f64),
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            OleValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+    pub fn as_object(&self) -> Option<&OleData> {
+        match self {
+            OleValue::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+    pub fn into_object(self) -> Option<OleData> {
+        match self {
+            OleValue::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+}
+
+/// Takes ownership of the `VARIANT`, clearing it once its data is copied.
+impl TryFrom<VARIANT> for OleValue {
+    type Error = Error;
+
+    #[track_caller]
+    fn try_from(mut variant: VARIANT) -> Result<OleValue> {
+        let value = OleValue::try_from(&variant);
+        unsafe { VariantClear(&mut variant)? };
+        value
+    }
+}
+
+impl TryFrom<&VARIANT> for OleValue {
+    type Error = Error;
+
+    #[track_caller]
+    fn try_from(variant: &VARIANT) -> Result<OleValue> {
+        let mut variant = variant;
+        let mut vt = unsafe { variant.Anonymous.Anonymous.vt };
+        // Follow `VT_BYREF | VT_VARIANT` chains to the VARIANT holding the data.
+        while vt.0 == VT_BYREF.0 | VT_VARIANT.0 {
+            let inner = unsafe { variant.Anonymous.Anonymous.Anonymous.pvarVal };
+            if inner.is_null() {
+                return Ok(OleValue::Empty);
+            }
+            variant = unsafe { &*inner };
+            vt = unsafe { variant.Anonymous.Anonymous.vt };
+        }
+        let data = unsafe { &variant.Anonymous.Anonymous.Anonymous };
+        if vt.0 & VT_BYREF.0 != 0 {
+            return unsafe { byref_value(VARENUM(vt.0 & !VT_BYREF.0), data) };
+        }
+        let value = unsafe {
+            match vt {
+                VT_EMPTY => OleValue::Empty,
+                VT_NULL => OleValue::Null,
+                VT_BOOL => OleValue::Bool(data.boolVal.0 != 0),
+                VT_I1 => OleValue::I1(data.cVal),
+                VT_I2 => OleValue::I2(data.iVal),
+                VT_I4 | VT_INT => OleValue::I4(data.lVal),
+                VT_I8 => OleValue::I8(data.llVal),
+                VT_UI1 => OleValue::UI1(data.bVal),
+                VT_UI2 => OleValue::UI2(data.uiVal),
+                VT_UI4 | VT_UINT => OleValue::UI4(data.ulVal),
+                VT_UI8 => OleValue::UI8(data.ullVal),
+                VT_R4 => OleValue::R4(data.fltVal),
+                VT_R8 => OleValue::R8(data.dblVal),
+                VT_CY => OleValue::Currency(data.cyVal.int64),
+                VT_DATE => OleValue::Date(data.date),
+                VT_BSTR => OleValue::String(String::try_from(&*data.bstrVal)?),
+                VT_ERROR => OleValue::Error(data.scode),
+                VT_DISPATCH => dispatch_value((*data.pdispVal).clone()),
+                VT_UNKNOWN => unknown_value((*data.punkVal).clone()),
+                VARENUM(vt) => {
+                    return Err(Error::Custom(format!(
+                        "VARIANT type {vt} cannot be converted to OleValue"
+                    )))
+                }
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// Read the value a `VT_BYREF` VARIANT points to; `vt` has `VT_BYREF` removed.
+#[track_caller]
+unsafe fn byref_value(vt: VARENUM, data: &VARIANT_0_0_0) -> Result<OleValue> {
+    if data.byref.is_null() {
+        return Ok(OleValue::Empty);
+    }
+    let value = match vt {
+        VT_BOOL => OleValue::Bool((*data.pboolVal).0 != 0),
+        VT_I1 => OleValue::I1(*data.pcVal.0 as i8),
+        VT_I2 => OleValue::I2(*data.piVal),
+        VT_I4 | VT_INT => OleValue::I4(*data.plVal),
+        VT_I8 => OleValue::I8(*data.pllVal),
+        VT_UI1 => OleValue::UI1(*data.pbVal),
+        VT_UI2 => OleValue::UI2(*data.puiVal),
+        VT_UI4 | VT_UINT => OleValue::UI4(*data.pulVal),
+        VT_UI8 => OleValue::UI8(*data.pullVal),
+        VT_R4 => OleValue::R4(*data.pfltVal),
+        VT_R8 => OleValue::R8(*data.pdblVal),
+        VT_CY => OleValue::Currency((*data.pcyVal).int64),
+        VT_DATE => OleValue::Date(*data.pdate),
+        VT_BSTR => OleValue::String(String::try_from(&*data.pbstrVal)?),
+        VT_ERROR => OleValue::Error(*data.pscode),
+        VT_DISPATCH => dispatch_value((*data.ppdispVal).clone()),
+        VT_UNKNOWN => unknown_value((*data.ppunkVal).clone()),
+        VARENUM(vt) => {
+            return Err(Error::Custom(format!(
+                "VARIANT type {} cannot be converted to OleValue",
+                vt | VT_BYREF.0
+            )))
+        }
+    };
+    Ok(value)
+}
+
+// A null interface pointer is VB's `Nothing`.
+#[track_caller]
+fn dispatch_value(dispatch: Option<IDispatch>) -> OleValue {
+    match dispatch {
+        Some(dispatch) => OleValue::Object(OleData::from_dispatch(dispatch)),
+        None => OleValue::Nothing,
+    }
+}
+
+#[track_caller]
+fn unknown_value(unknown: Option<IUnknown>) -> OleValue {
+    match unknown {
+        Some(unknown) => match unknown.cast::<IDispatch>() {
+            Ok(dispatch) => OleValue::Object(OleData::from_dispatch(dispatch)),
+            Err(_) => OleValue::Unknown(unknown),
+        },
+        None => OleValue::Nothing,
+    }
+}
+
+impl From<&OleValue> for VARIANT {
+    fn from(value: &OleValue) -> VARIANT {
+        match value {
+            OleValue::Empty => VARIANT::default(),
+            OleValue::Bool(v) => VARIANT::from(*v),
+            OleValue::I1(v) => VARIANT::from(*v),
+            OleValue::I2(v) => VARIANT::from(*v),
+            OleValue::I4(v) => VARIANT::from(*v),
+            OleValue::I8(v) => VARIANT::from(*v),
+            OleValue::UI1(v) => VARIANT::from(*v),
+            OleValue::UI2(v) => VARIANT::from(*v),
+            OleValue::UI4(v) => VARIANT::from(*v),
+            OleValue::UI8(v) => VARIANT::from(*v),
+            OleValue::R4(v) => VARIANT::from(*v),
+            OleValue::R8(v) => VARIANT::from(*v),
+            OleValue::String(v) => VARIANT::from(BSTR::from(v.as_str())),
+            OleValue::Object(v) => VARIANT::from(v.dispatch.clone()),
+            OleValue::Unknown(v) => VARIANT::from(v.clone()),
+            OleValue::Null => raw_variant(VT_NULL, |_| {}),
+            OleValue::Nothing => raw_variant(VT_DISPATCH, |_| {}),
+            OleValue::Currency(v) => raw_variant(VT_CY, |data| data.cyVal.int64 = *v),
+            OleValue::Date(v) => raw_variant(VT_DATE, |data| data.date = *v),
+            OleValue::Error(v) => raw_variant(VT_ERROR, |data| data.scode = *v),
+        }
+    }
+}
+
+fn raw_variant<F: FnOnce(&mut VARIANT_0_0_0)>(vt: VARENUM, fill: F) -> VARIANT {
+    let mut variant = VARIANT::default();
+    unsafe {
+        (*variant.Anonymous.Anonymous).vt = vt;
+        fill(&mut (*variant.Anonymous.Anonymous).Anonymous);
+    }
+    variant
+}
+
+impl fmt::Display for OleValue {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OleValue::Empty => write!(fmt, "Empty"),
+            OleValue::Null => write!(fmt, "Null"),
+            OleValue::Bool(v) => write!(fmt, "{v}"),
+            OleValue::I1(v) => write!(fmt, "{v}"),
+            OleValue::I2(v) => write!(fmt, "{v}"),
+            OleValue::I4(v) => write!(fmt, "{v}"),
+            OleValue::I8(v) => write!(fmt, "{v}"),
+            OleValue::UI1(v) => write!(fmt, "{v}"),
+            OleValue::UI2(v) => write!(fmt, "{v}"),
+            OleValue::UI4(v) => write!(fmt, "{v}"),
+            OleValue::UI8(v) => write!(fmt, "{v}"),
+            OleValue::R4(v) => write!(fmt, "{v}"),
+            OleValue::R8(v) => write!(fmt, "{v}"),
+            OleValue::Currency(v) => write!(fmt, "{}", *v as f64 / 10_000.0),
+            OleValue::Date(v) => write!(fmt, "{v}"),
+            OleValue::String(v) => write!(fmt, "{v}"),
+            OleValue::Error(v) => write!(fmt, "Error({v:#010X})"),
+            OleValue::Object(_) => write!(fmt, "Object"),
+            OleValue::Unknown(_) => write!(fmt, "Unknown"),
+            OleValue::Nothing => write!(fmt, "Nothing"),
+        }
+    }
+}
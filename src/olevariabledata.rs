@@ -1,17 +1,19 @@
-use std::ptr::{self, NonNull};
+use std::ptr::NonNull;
 
-use windows::{
-    core::BSTR,
-    Win32::System::{
-        Com::{
-            ITypeInfo, TYPEDESC, VARDESC, VARFLAG_FHIDDEN, VARFLAG_FNONBROWSABLE,
-            VARFLAG_FRESTRICTED, VARKIND, VAR_CONST, VAR_DISPATCH, VAR_PERINSTANCE, VAR_STATIC,
-        },
-        Variant::VARIANT,
+use windows::Win32::System::{
+    Com::{
+        ITypeInfo, TYPEDESC, VARDESC, VARFLAG_FHIDDEN, VARFLAG_FNONBROWSABLE, VARFLAG_FRESTRICTED,
+        VARKIND, VAR_CONST, VAR_DISPATCH, VAR_PERINSTANCE, VAR_STATIC,
     },
+    Variant::VARIANT,
 };
 
 use crate::{
+    custdata::{format_custom_attributes, var_custom_data, CustomData},
+    documentation::{
+        typeinfo_documentation, typeinfo_localized_documentation, Documentation, Documented,
+        LocalizedDocumentation,
+    },
     error::Result,
     util::ole::{TypeRef, ValueDescription},
 };
@@ -83,56 +85,27 @@ impl OleVariableData {
     pub fn member_id(&self) -> i32 {
         unsafe { self.var_desc.as_ref().memid }
     }
-    fn docinfo(
-        &self,
-        name: Option<*mut BSTR>,
-        helpstr: Option<*mut BSTR>,
-        helpcontext: *mut u32,
-        helpfile: Option<*mut BSTR>,
-    ) -> Result<()> {
-        unsafe {
-            self.typeinfo.GetDocumentation(
-                self.var_desc.as_ref().memid,
-                name,
-                helpstr,
-                helpcontext,
-                helpfile,
-            )?
-        };
-        Ok(())
-    }
-    pub fn get_documentation(&self) -> Result<(String, String, u32, String)> {
-        let mut strname = BSTR::default();
-        let mut strdocstring = BSTR::default();
-        let mut whelpcontext = 0;
-        let mut strhelpfile = BSTR::default();
-        self.docinfo(
-            Some(&mut strname),
-            Some(&mut strdocstring),
-            &mut whelpcontext,
-            Some(&mut strhelpfile),
-        )?;
-        Ok((
-            String::try_from(strname)?,
-            String::try_from(strdocstring)?,
-            whelpcontext,
-            String::try_from(strhelpfile)?,
-        ))
-    }
-    pub fn helpstring(&self) -> Result<String> {
-        let mut helpstring = BSTR::default();
-        self.docinfo(None, Some(&mut helpstring), ptr::null_mut(), None)?;
-        Ok(String::try_from(helpstring)?)
-    }
-    pub fn helpfile(&self) -> Result<String> {
-        let mut helpfile = BSTR::default();
-        self.docinfo(None, None, ptr::null_mut(), Some(&mut helpfile))?;
-        Ok(String::try_from(helpfile)?)
-    }
-    pub fn helpcontext(&self) -> Result<u32> {
-        let mut helpcontext = 0;
-        self.docinfo(None, None, &mut helpcontext, None)?;
-        Ok(helpcontext)
+    pub fn custom_data(&self) -> Result<CustomData> {
+        var_custom_data(&self.typeinfo, self.member_id())
+    }
+    /// The IDL attributes of the variable, e.g. `["id(0x00000001)", "hidden"]`.
+    ///
+    pub fn idl_attributes(&self) -> Result<Vec<String>> {
+        let varflags = unsafe { self.var_desc.as_ref().wVarFlags.0 };
+        let flags = [
+            (VARFLAG_FHIDDEN.0, "hidden"),
+            (VARFLAG_FRESTRICTED.0, "restricted"),
+            (VARFLAG_FNONBROWSABLE.0, "nonbrowsable"),
+        ];
+        let mut attributes = vec![format!("id({:#010x})", self.member_id())];
+        attributes.extend(
+            flags
+                .iter()
+                .filter(|(flag, _)| varflags & flag != 0)
+                .map(|(_, name)| String::from(*name)),
+        );
+        attributes.extend(format_custom_attributes(&self.custom_data()?));
+        Ok(attributes)
     }
 }
 
@@ -152,3 +125,12 @@ impl TypeRef for OleVariableData {
 }
 
 impl ValueDescription for OleVariableData {}
+
+impl Documented for OleVariableData {
+    fn documentation(&self) -> Result<Documentation> {
+        typeinfo_documentation(&self.typeinfo, self.member_id())
+    }
+    fn localized_documentation(&self, lcid: u32) -> Result<LocalizedDocumentation> {
+        typeinfo_localized_documentation(&self.typeinfo, self.member_id(), lcid)
+    }
+}
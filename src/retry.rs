@@ -0,0 +1,234 @@
+use std::{
+    cell::Cell,
+    marker::PhantomData,
+    sync::{Arc, RwLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use windows::{
+    core::implement,
+    Win32::{
+        Media::{
+            Audio::{CoRegisterMessageFilter, IMessageFilter, IMessageFilter_Impl},
+            HTASK,
+        },
+        System::Com::{
+            INTERFACEINFO, PENDINGMSG_WAITDEFPROCESS, SERVERCALL_ISHANDLED, SERVERCALL_REJECTED,
+            SERVERCALL_RETRYLATER,
+        },
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    error::{Error, Result},
+    hresult::HResult,
+};
+
+static DEFAULT_POLICY: RwLock<Option<Arc<RetryPolicy>>> = RwLock::new(None);
+
+thread_local! {
+    /// Number of [`MessageFilterGuard`]s alive on this thread.
+    static MESSAGE_FILTERS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Whether a [`MessageFilterGuard`] is registered on the current thread, in
+/// which case COM already retries calls the server rejects as busy.
+pub(crate) fn message_filter_registered() -> bool {
+    MESSAGE_FILTERS.with(|filters| filters.get() > 0)
+}
+
+/// How long and how often to retry calls that an out-of-process server
+/// rejected because it was busy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    max_wait: Duration,
+    retryable: Vec<HResult>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            max_wait: Duration::from_secs(30),
+            retryable: vec![
+                HResult::RPC_E_CALL_REJECTED,
+                HResult::RPC_E_SERVERCALL_RETRYLATER,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> RetryPolicy {
+        RetryPolicy::default()
+    }
+    /// Delay before the first retry. Each further delay is multiplied by the
+    /// backoff multiplier, up to the maximum delay.
+    ///
+    pub fn with_initial_delay(mut self, delay: Duration) -> RetryPolicy {
+        self.initial_delay = delay;
+        self
+    }
+    pub fn with_max_delay(mut self, delay: Duration) -> RetryPolicy {
+        self.max_delay = delay;
+        self
+    }
+    pub fn with_multiplier(mut self, multiplier: f64) -> RetryPolicy {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+    /// Give up once a call has been failing for `max_wait`.
+    ///
+    pub fn with_max_wait(mut self, max_wait: Duration) -> RetryPolicy {
+        self.max_wait = max_wait;
+        self
+    }
+    /// Replace the list of HRESULTs that are retried.
+    ///
+    pub fn with_retryable(mut self, retryable: Vec<HResult>) -> RetryPolicy {
+        self.retryable = retryable;
+        self
+    }
+    pub fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+    pub fn max_wait(&self) -> Duration {
+        self.max_wait
+    }
+    pub fn retryable(&self) -> &[HResult] {
+        &self.retryable
+    }
+    pub fn is_retryable(&self, hresult: HResult) -> bool {
+        self.retryable.contains(&hresult)
+    }
+    /// Run `f` until it succeeds, fails with an error that is not retryable,
+    /// or keeps failing for longer than the maximum wait.
+    ///
+    pub fn run<T, F: FnMut() -> Result<T>>(&self, mut f: F) -> Result<T> {
+        let start = Instant::now();
+        let mut delay = self.initial_delay;
+        loop {
+            match f() {
+                Err(error) if self.should_retry(&error, start.elapsed() + delay) => {
+                    thread::sleep(delay);
+                    delay = self.next_delay(delay);
+                }
+                result => return result,
+            }
+        }
+    }
+    fn should_retry(&self, error: &Error, elapsed: Duration) -> bool {
+        elapsed <= self.max_wait && error.hresult().is_some_and(|hr| self.is_retryable(hr))
+    }
+    fn next_delay(&self, delay: Duration) -> Duration {
+        delay.mul_f64(self.multiplier).min(self.max_delay)
+    }
+    /// Delay to ask for from `IMessageFilter::RetryRejectedCall` once a call
+    /// has been rejected for `elapsed`.
+    fn filter_delay(&self, elapsed: Duration) -> Duration {
+        let mut delay = self.initial_delay;
+        let mut waited = Duration::ZERO;
+        while waited + delay <= elapsed && delay < self.max_delay {
+            waited += delay;
+            delay = self.next_delay(delay);
+        }
+        delay
+    }
+    /// The policy used by every [`OleData`](crate::OleData) that does not
+    /// have one of its own. There is none unless one has been set.
+    ///
+    pub fn default_policy() -> Option<Arc<RetryPolicy>> {
+        DEFAULT_POLICY.read().unwrap().clone()
+    }
+    pub fn set_default_policy(policy: Option<RetryPolicy>) {
+        *DEFAULT_POLICY.write().unwrap() = policy.map(Arc::new);
+    }
+}
+
+#[implement(IMessageFilter)]
+struct MessageFilter {
+    policy: RetryPolicy,
+}
+
+impl IMessageFilter_Impl for MessageFilter_Impl {
+    fn HandleInComingCall(
+        &self,
+        _dwcalltype: u32,
+        _htaskcaller: HTASK,
+        _dwtickcount: u32,
+        _lpinterfaceinfo: *const INTERFACEINFO,
+    ) -> u32 {
+        SERVERCALL_ISHANDLED.0 as u32
+    }
+
+    fn RetryRejectedCall(&self, _htaskcallee: HTASK, dwtickcount: u32, dwrejecttype: u32) -> u32 {
+        let hresult = if dwrejecttype == SERVERCALL_RETRYLATER.0 as u32 {
+            HResult::RPC_E_SERVERCALL_RETRYLATER
+        } else if dwrejecttype == SERVERCALL_REJECTED.0 as u32 {
+            HResult::RPC_E_SERVERCALL_REJECTED
+        } else {
+            return u32::MAX;
+        };
+        let elapsed = Duration::from_millis(dwtickcount as u64);
+        if elapsed > self.policy.max_wait || !self.policy.is_retryable(hresult) {
+            // Cancel the call; the caller sees RPC_E_CALL_REJECTED.
+            return u32::MAX;
+        }
+        // Values below 100 mean "retry immediately".
+        self.policy
+            .filter_delay(elapsed)
+            .as_millis()
+            .min(u32::MAX as u128 - 1) as u32
+    }
+
+    fn MessagePending(&self, _htaskcallee: HTASK, _dwtickcount: u32, _dwpendingtype: u32) -> u32 {
+        PENDINGMSG_WAITDEFPROCESS.0 as u32
+    }
+}
+
+/// An `IMessageFilter` registered for the current single-threaded apartment.
+///
+/// While registered, calls that the server rejects as busy are retried by COM
+/// itself according to the policy, before [`OleData`](crate::OleData) sees any
+/// error. [`OleData`](crate::OleData) does not apply its own retry policy on
+/// top of the filter, so a call waits at most the filter's `max_wait`.
+/// Dropping the guard restores the previously registered filter.
+pub struct MessageFilterGuard {
+    previous: Option<IMessageFilter>,
+    _ptr: PhantomData<*mut ()>,
+}
+
+impl MessageFilterGuard {
+    pub fn register(policy: RetryPolicy) -> Result<MessageFilterGuard> {
+        ensure_apartment()?;
+        let filter: IMessageFilter = MessageFilter { policy }.into();
+        let mut previous = None;
+        unsafe { CoRegisterMessageFilter(&filter, Some(&mut previous as *mut _))? };
+        MESSAGE_FILTERS.with(|filters| filters.set(filters.get() + 1));
+        Ok(MessageFilterGuard {
+            previous,
+            _ptr: PhantomData,
+        })
+    }
+}
+
+impl Drop for MessageFilterGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let _ = unsafe { CoRegisterMessageFilter(previous.as_ref(), None) };
+        MESSAGE_FILTERS.with(|filters| filters.set(filters.get() - 1));
+    }
+}
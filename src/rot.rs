@@ -0,0 +1,88 @@
+use windows::{
+    core::Interface,
+    Win32::System::Com::{
+        CoTaskMemFree, CreateBindCtx, GetRunningObjectTable, IDispatch, IMoniker,
+        IRunningObjectTable,
+    },
+};
+
+use crate::{
+    apartment::ensure_apartment,
+    error::{OleError, Result},
+    OleData,
+};
+
+/// An object registered in the Running Object Table.
+pub struct RunningObject {
+    moniker: IMoniker,
+    display_name: String,
+}
+
+impl RunningObject {
+    /// Display name of the registered moniker, e.g. the path of an open
+    /// workbook or `!{00024500-0000-0000-C000-000000000046}` for an
+    /// application registered with `RegisterActiveObject`.
+    ///
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+    pub fn moniker(&self) -> &IMoniker {
+        &self.moniker
+    }
+    /// Retrieve the running object and wrap it as an [`OleData`].
+    ///
+    #[track_caller]
+    pub fn bind(&self) -> Result<OleData> {
+        let rot = running_object_table()?;
+        let unknown = unsafe { rot.GetObject(&self.moniker)? };
+        match unknown.cast::<IDispatch>() {
+            Ok(dispatch) => Ok(OleData::from_dispatch(dispatch)),
+            Err(error) => Err(OleError::interface(
+                error,
+                format!("failed to get IDispatch of `{}`", self.display_name),
+            )
+            .into()),
+        }
+    }
+}
+
+/// List every object currently registered in the Running Object Table.
+///
+/// Entries whose moniker cannot produce a display name, e.g. because the
+/// registering process has exited without revoking it, are skipped.
+pub fn running_objects() -> Result<Vec<RunningObject>> {
+    let rot = running_object_table()?;
+    let bind_ctx = unsafe { CreateBindCtx(0)? };
+    let monikers = unsafe { rot.EnumRunning()? };
+
+    let mut objects = vec![];
+    loop {
+        let mut moniker = [None];
+        let mut fetched = 0;
+        unsafe { monikers.Next(&mut moniker, Some(&mut fetched)).ok()? };
+        if fetched == 0 {
+            break;
+        }
+        let [Some(moniker)] = moniker else {
+            continue;
+        };
+        let Ok(name) = (unsafe { moniker.GetDisplayName(&bind_ctx, None) }) else {
+            continue;
+        };
+        let display_name = unsafe { name.to_string() };
+        unsafe { CoTaskMemFree(Some(name.0 as *const _)) };
+        let Ok(display_name) = display_name else {
+            continue;
+        };
+        objects.push(RunningObject {
+            moniker,
+            display_name,
+        });
+    }
+    Ok(objects)
+}
+
+fn running_object_table() -> Result<IRunningObjectTable> {
+    ensure_apartment()?;
+    Ok(unsafe { GetRunningObjectTable(0)? })
+}
@@ -0,0 +1,110 @@
+use std::{fmt, panic::Location, thread::ThreadId, time::Instant};
+
+/// An [`OleData`](crate::OleData) that has not been dropped yet, as recorded
+/// by the debug-build reference tracker.
+#[derive(Debug, Clone)]
+pub struct LiveObject {
+    pub id: u64,
+    /// Where the `OleData` was created or cloned.
+    pub location: &'static Location<'static>,
+    pub thread_id: ThreadId,
+    pub thread_name: Option<String>,
+    pub created: Instant,
+    /// Address of the `IDispatch` interface, to spot references to the same
+    /// object.
+    pub dispatch: usize,
+}
+
+impl fmt::Display for LiveObject {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "#{} IDispatch {:#x} created at {} on thread {} ({:?} ago)",
+            self.id,
+            self.dispatch,
+            self.location,
+            self.thread_name.as_deref().unwrap_or("<unnamed>"),
+            self.created.elapsed()
+        )
+    }
+}
+
+/// Every `OleData` that is still alive, oldest first.
+///
+/// Only debug builds track objects; in release builds the list is always
+/// empty.
+pub fn live_objects() -> Vec<LiveObject> {
+    imp::live_objects()
+}
+
+pub(crate) use imp::Tracker;
+
+#[cfg(debug_assertions)]
+mod imp {
+    use std::{
+        collections::BTreeMap,
+        panic::Location,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        thread,
+        time::Instant,
+    };
+
+    use super::LiveObject;
+
+    static LIVE: Mutex<BTreeMap<u64, LiveObject>> = Mutex::new(BTreeMap::new());
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+    /// Registers its `OleData` with the tracker for as long as it lives.
+    pub(crate) struct Tracker {
+        id: u64,
+    }
+
+    impl Tracker {
+        #[track_caller]
+        pub(crate) fn new(dispatch: usize) -> Tracker {
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            let current = thread::current();
+            let object = LiveObject {
+                id,
+                location: Location::caller(),
+                thread_id: current.id(),
+                thread_name: current.name().map(str::to_string),
+                created: Instant::now(),
+                dispatch,
+            };
+            LIVE.lock().unwrap().insert(id, object);
+            Tracker { id }
+        }
+    }
+
+    impl Drop for Tracker {
+        fn drop(&mut self) {
+            LIVE.lock().unwrap().remove(&self.id);
+        }
+    }
+
+    pub(super) fn live_objects() -> Vec<LiveObject> {
+        LIVE.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[cfg(not(debug_assertions))]
+mod imp {
+    use super::LiveObject;
+
+    pub(crate) struct Tracker;
+
+    impl Tracker {
+        #[inline]
+        pub(crate) fn new(_dispatch: usize) -> Tracker {
+            Tracker
+        }
+    }
+
+    pub(super) fn live_objects() -> Vec<LiveObject> {
+        vec![]
+    }
+}
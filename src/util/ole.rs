@@ -1,85 +1,34 @@
 use crate::{
-    error::{OleError, Result},
-    ToWide, G_RUNNING_NANO,
+    apartment::ensure_apartment,
+    error::{Error, OleError, Result},
+    ToWide,
 };
-use std::{ffi::OsStr, marker::PhantomData, ptr};
+use std::{ffi::OsStr, ptr};
 use windows::{
-    core::{Interface, BSTR, GUID, PCWSTR},
-    Win32::{
-        Foundation::RPC_E_CHANGED_MODE,
-        System::{
-            Com::{
-                CLSIDFromProgID, CLSIDFromString, CoCreateInstance, CoIncrementMTAUsage,
-                CoInitializeEx, CoUninitialize, ITypeInfo, ITypeLib, CLSCTX_INPROC_SERVER,
-                CLSCTX_LOCAL_SERVER, COINIT_MULTITHREADED, CO_MTA_USAGE_COOKIE, TYPEDESC,
-            },
-            Ole::{OleInitialize, OleUninitialize},
-            Variant::{VT_PTR, VT_SAFEARRAY},
+    core::{IUnknown, Interface, BSTR, GUID, PCWSTR},
+    Win32::System::{
+        Com::{
+            CLSIDFromProgID, CLSIDFromString, CoCreateInstance, CoIncrementMTAUsage, ITypeInfo,
+            ITypeInfo2, ITypeLib, ITypeLib2, CLSCTX_INPROC_SERVER, CLSCTX_LOCAL_SERVER,
+            CO_MTA_USAGE_COOKIE, TYPEDESC,
         },
+        Ole::GetActiveObject,
+        Variant::{VT_PTR, VT_SAFEARRAY},
     },
 };
 
 /// Initialize a new multithreaded apartment (MTA) runtime. This will ensure
 /// that an MTA is running for the process. Every new thread will implicitly
-/// be in the MTA unless a different apartment type is chosen (through [`init_apartment`])
+/// be in the MTA unless a different apartment type is chosen (through [`Apartment`](crate::Apartment))
 ///
 /// This calls `CoIncrementMTAUsage`
 ///
-/// This function only needs to be called once per process.
+/// This function only needs to be called once per process. Threads that use
+/// this crate's objects still have to enter an apartment themselves.
 pub fn init_runtime() -> windows::core::Result<CO_MTA_USAGE_COOKIE> {
     unsafe { CoIncrementMTAUsage() }
 }
 
-thread_local!(static OLE_INITIALIZED: OleInitialized = {
-    unsafe {
-        let result = if *G_RUNNING_NANO {
-            CoInitializeEx(None, COINIT_MULTITHREADED).ok()
-        } else {
-            OleInitialize(None)
-        };
-        match result.clone().map_err(|e| e.code()) {
-            Ok(_) | Err(RPC_E_CHANGED_MODE) => {
-                OleInitialized {
-                    result,
-                    _ptr: PhantomData,
-                }
-            },
-            Err(error) => {
-                let runtime_error = OleError::runtime(error, "failed: OLE initialization");
-                panic!("{runtime_error}");
-            }
-        }
-    }
-});
-
-/// RAII object that guards the fact that COM is initialized.
-///
-// We store a raw pointer because it's the only way at the moment to remove `Send`/`Sync` from the
-// object.
-struct OleInitialized {
-    result: windows::core::Result<()>,
-    _ptr: PhantomData<*mut ()>,
-}
-
-impl Drop for OleInitialized {
-    #[inline]
-    fn drop(&mut self) {
-        if self.result.is_ok() {
-            if *G_RUNNING_NANO {
-                unsafe { CoUninitialize() };
-            } else {
-                unsafe { OleUninitialize() };
-            }
-        }
-    }
-}
-
-/// Ensures that COM is initialized in this thread.
-#[inline]
-pub fn ole_initialized() {
-    OLE_INITIALIZED.with(|_| {});
-}
-
 pub fn get_class_id<S: AsRef<OsStr>>(s: S) -> Result<GUID> {
     let prog_id = s.to_wide_null();
     let prog_id = PCWSTR::from_raw(prog_id.as_ptr());
@@ -105,12 +54,35 @@ pub fn create_instance<T: Interface>(clsid: &GUID) -> Result<T> {
 }
 
 pub fn create_com_object<S: AsRef<OsStr>, T: Interface>(s: S) -> Result<T> {
-    ole_initialized();
+    ensure_apartment()?;
     let class_id = get_class_id(s)?;
 
     create_instance(&class_id)
 }
 
+/// Attach to a running instance of the server registered in the Running
+/// Object Table with `RegisterActiveObject`.
+pub fn get_active_object<S: AsRef<OsStr>, T: Interface>(s: S) -> Result<T> {
+    ensure_apartment()?;
+    let class_id = get_class_id(&s)?;
+
+    let mut unknown: Option<IUnknown> = None;
+    if let Err(error) = unsafe { GetActiveObject(&class_id, None, &mut unknown) } {
+        return Err(OleError::runtime(
+            error,
+            format!("OLE server `{}` not running", s.as_ref().to_string_lossy()),
+        )
+        .into());
+    }
+    let unknown = unknown.ok_or(Error::Generic("GetActiveObject returned a null object"))?;
+    match unknown.cast::<T>() {
+        Ok(object) => Ok(object),
+        Err(error) => {
+            Err(OleError::interface(error, "failed to get interface of running object").into())
+        }
+    }
+}
+
 pub trait TypeRef {
     fn typeinfo(&self) -> &ITypeInfo;
     fn typedesc(&self) -> &TYPEDESC;
@@ -234,3 +206,17 @@ pub(crate) fn ole_docinfo(
     unsafe { typelib.GetDocumentation(index as i32, name, helpstr, helpcontext, helpfile)? };
     Ok(())
 }
+
+pub(crate) fn typeinfo2(typeinfo: &ITypeInfo) -> Result<ITypeInfo2> {
+    match typeinfo.cast::<ITypeInfo2>() {
+        Ok(typeinfo2) => Ok(typeinfo2),
+        Err(error) => Err(OleError::interface(error, "failed to query ITypeInfo2").into()),
+    }
+}
+
+pub(crate) fn typelib2(typelib: &ITypeLib) -> Result<ITypeLib2> {
+    match typelib.cast::<ITypeLib2>() {
+        Ok(typelib2) => Ok(typelib2),
+        Err(error) => Err(OleError::interface(error, "failed to query ITypeLib2").into()),
+    }
+}
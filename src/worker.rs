@@ -0,0 +1,836 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Condvar, Mutex, Weak,
+    },
+    task::{Context, Poll, Waker},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use windows::Win32::{
+    Foundation::{LPARAM, WPARAM},
+    System::{
+        Com::{DISPATCH_METHOD, DISPATCH_PROPERTYGET},
+        Threading::GetCurrentThreadId,
+        Variant::VARIANT,
+    },
+    UI::WindowsAndMessaging::{
+        DispatchMessageW, GetMessageW, PeekMessageW, PostThreadMessageW, TranslateMessage, MSG,
+        PM_NOREMOVE, WM_APP, WM_QUIT, WM_USER,
+    },
+};
+
+use crate::{
+    agile::AgileOleData,
+    apartment::{ApartmentBuilder, ApartmentType},
+    error::{Error, Result},
+    olevalue::OleValue,
+    OleData,
+};
+
+/// Posted to the worker thread to wake its message loop when a job is queued.
+const WM_WORKER_JOB: u32 = WM_APP + 1;
+
+type Job = Box<dyn FnOnce(&mut Objects) + Send>;
+
+/// A thread in its own single-threaded apartment that owns automation objects
+/// and runs requests against them.
+///
+/// The thread keeps a message loop running between requests, as Office and
+/// other STA servers require. Requests are queued through cloneable
+/// [`WorkerHandle`]s and answered with [`Response`] futures. Dropping the
+/// worker shuts the thread down and waits for it to exit.
+pub struct StaWorker {
+    handle: WorkerHandle,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StaWorker {
+    pub fn spawn() -> Result<StaWorker> {
+        StaWorkerBuilder::new().spawn()
+    }
+    pub fn builder() -> StaWorkerBuilder {
+        StaWorkerBuilder::new()
+    }
+    pub fn handle(&self) -> WorkerHandle {
+        self.handle.clone()
+    }
+    /// Stop the message loop and wait for the thread to exit. Queued requests
+    /// that have not started fail, and every object held by the worker is
+    /// released on its thread.
+    ///
+    pub fn shutdown(mut self) -> Result<()> {
+        self.stop()
+    }
+    fn stop(&mut self) -> Result<()> {
+        self.handle.shutdown();
+        match self.thread.take() {
+            Some(thread) => thread
+                .join()
+                .map_err(|_| Error::Generic("STA worker thread panicked")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for StaWorker {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+pub struct StaWorkerBuilder {
+    ole: bool,
+    timeout: Option<Duration>,
+    name: Option<String>,
+}
+
+impl Default for StaWorkerBuilder {
+    fn default() -> Self {
+        StaWorkerBuilder {
+            ole: true,
+            timeout: None,
+            name: None,
+        }
+    }
+}
+
+impl StaWorkerBuilder {
+    pub fn new() -> StaWorkerBuilder {
+        StaWorkerBuilder::default()
+    }
+    /// Initialize the thread through `OleInitialize` rather than
+    /// `CoInitializeEx`. Enabled by default.
+    ///
+    pub fn ole(mut self, ole: bool) -> StaWorkerBuilder {
+        self.ole = ole;
+        self
+    }
+    /// Default timeout for every request made through the worker's handles.
+    ///
+    pub fn timeout(mut self, timeout: Duration) -> StaWorkerBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+    pub fn name<S: Into<String>>(mut self, name: S) -> StaWorkerBuilder {
+        self.name = Some(name.into());
+        self
+    }
+    pub fn spawn(self) -> Result<StaWorker> {
+        let (sender, receiver) = mpsc::channel();
+        let (ready_sender, ready) = mpsc::sync_channel(1);
+        let ole = self.ole;
+        let mut builder = thread::Builder::new();
+        if let Some(name) = self.name {
+            builder = builder.name(name);
+        }
+        let thread = builder.spawn(move || run_worker(receiver, ready_sender, ole))?;
+
+        let thread_id = match ready.recv() {
+            Ok(Ok(thread_id)) => thread_id,
+            Ok(Err(error)) => {
+                let _ = thread.join();
+                return Err(error);
+            }
+            Err(_) => {
+                let _ = thread.join();
+                return Err(Error::Generic("STA worker thread exited during startup"));
+            }
+        };
+        Ok(StaWorker {
+            handle: WorkerHandle {
+                shared: Arc::new(Shared {
+                    sender: Mutex::new(Some(sender)),
+                    thread_id,
+                    timer: Arc::new(Timer::default()),
+                }),
+                timeout: self.timeout,
+            },
+            thread: Some(thread),
+        })
+    }
+}
+
+fn run_worker(receiver: Receiver<Job>, ready: mpsc::SyncSender<Result<u32>>, ole: bool) {
+    let apartment = ApartmentBuilder::new(ApartmentType::SingleThreaded)
+        .ole(ole)
+        .init();
+    let apartment = match apartment {
+        Ok(apartment) => apartment,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+    // Create the thread's message queue before anyone can post to it.
+    let mut msg = MSG::default();
+    let _ = unsafe { PeekMessageW(&mut msg, None, WM_USER, WM_USER, PM_NOREMOVE) };
+    let _ = ready.send(Ok(unsafe { GetCurrentThreadId() }));
+
+    let mut objects = Objects::default();
+    loop {
+        while let Ok(job) = receiver.try_recv() {
+            job(&mut objects);
+        }
+        match unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 {
+            0 | -1 => break,
+            _ => unsafe {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            },
+        }
+    }
+    // Fail whatever is still queued, then release the objects while COM is
+    // still initialized.
+    drop(receiver);
+    drop(objects);
+    drop(apartment);
+}
+
+/// Objects owned by the worker thread, keyed by the id held in [`RemoteObject`].
+#[derive(Default)]
+struct Objects {
+    objects: HashMap<u64, OleData>,
+    next_id: u64,
+}
+
+impl Objects {
+    fn insert(&mut self, object: OleData) -> u64 {
+        self.next_id += 1;
+        self.objects.insert(self.next_id, object);
+        self.next_id
+    }
+    fn get(&self, id: u64) -> Result<&OleData> {
+        self.objects
+            .get(&id)
+            .ok_or(Error::Generic("object is no longer held by the STA worker"))
+    }
+}
+
+struct Shared {
+    sender: Mutex<Option<Sender<Job>>>,
+    thread_id: u32,
+    timer: Arc<Timer>,
+}
+
+impl Shared {
+    fn post(&self, job: Job) -> bool {
+        let sender = self.sender.lock().unwrap();
+        let Some(sender) = sender.as_ref() else {
+            return false;
+        };
+        if sender.send(job).is_err() {
+            return false;
+        }
+        unsafe { PostThreadMessageW(self.thread_id, WM_WORKER_JOB, WPARAM(0), LPARAM(0)) }.is_ok()
+    }
+    fn shutdown(&self) {
+        if self.sender.lock().unwrap().take().is_some() {
+            let _ = unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0)) };
+        }
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        self.timer.stop();
+    }
+}
+
+/// Fails requests whose timeout has passed. A single thread per worker, started
+/// by the first request with a timeout, waits for the earliest deadline.
+#[derive(Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct TimerState {
+    /// Keyed by deadline, then by insertion order to keep keys unique.
+    deadlines: BTreeMap<(Instant, u64), Weak<dyn Expire>>,
+    next_id: u64,
+    running: bool,
+    stopped: bool,
+}
+
+/// A request slot the [`Timer`] can fail.
+trait Expire: Send + Sync {
+    fn expire(&self);
+}
+
+impl Timer {
+    fn add(self: &Arc<Self>, deadline: Instant, slot: Weak<dyn Expire>) {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let key = (deadline, state.next_id);
+        state.deadlines.insert(key, slot);
+        if !state.running {
+            let timer = self.clone();
+            if thread::Builder::new().spawn(move || timer.run()).is_ok() {
+                state.running = true;
+            }
+        }
+        self.changed.notify_one();
+    }
+    fn stop(&self) {
+        self.state.lock().unwrap().stopped = true;
+        self.changed.notify_one();
+    }
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        while !state.stopped {
+            let now = Instant::now();
+            let mut expired = vec![];
+            while let Some(entry) = state.deadlines.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                expired.push(entry.remove());
+            }
+            if !expired.is_empty() {
+                // Complete the slots without holding the lock, since waking a
+                // task may queue another request.
+                drop(state);
+                for slot in expired {
+                    if let Some(slot) = slot.upgrade() {
+                        slot.expire();
+                    }
+                }
+                state = self.state.lock().unwrap();
+                continue;
+            }
+            state = match state.deadlines.keys().next() {
+                Some(&(deadline, _)) => self.changed.wait_timeout(state, deadline - now).unwrap().0,
+                None => self.changed.wait(state).unwrap(),
+            };
+        }
+    }
+}
+
+/// A cloneable, `Send + Sync` handle for queueing requests on an [`StaWorker`].
+#[derive(Clone)]
+pub struct WorkerHandle {
+    shared: Arc<Shared>,
+    timeout: Option<Duration>,
+}
+
+impl WorkerHandle {
+    /// A handle whose requests fail with a timeout error after `timeout`.
+    ///
+    /// A request that has already started on the worker thread cannot be
+    /// interrupted; only its response is abandoned.
+    ///
+    pub fn with_timeout(&self, timeout: Duration) -> WorkerHandle {
+        WorkerHandle {
+            shared: self.shared.clone(),
+            timeout: Some(timeout),
+        }
+    }
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+    pub fn is_running(&self) -> bool {
+        self.shared.sender.lock().unwrap().is_some()
+    }
+    /// Ask the worker to stop after the request it is currently running.
+    ///
+    pub fn shutdown(&self) {
+        self.shared.shutdown();
+    }
+    /// Run `f` on the worker thread.
+    ///
+    pub fn run<T, F>(&self, f: F) -> Response<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        self.submit(move |_| f())
+    }
+    /// Create an object with `OleData::new` and keep it on the worker thread.
+    ///
+    pub fn create<S: Into<String>>(&self, prog_id: S) -> Response<RemoteObject> {
+        let prog_id = prog_id.into();
+        let handle = self.clone();
+        self.submit(move |objects| {
+            let object = OleData::new(&prog_id)?;
+            Ok(RemoteObject::new(handle, objects.insert(object)))
+        })
+    }
+    /// Attach to a running object with `OleData::connect` and keep it on the
+    /// worker thread.
+    ///
+    pub fn connect<S: Into<String>>(&self, prog_id: S) -> Response<RemoteObject> {
+        let prog_id = prog_id.into();
+        let handle = self.clone();
+        self.submit(move |objects| {
+            let object = OleData::connect(&prog_id)?;
+            Ok(RemoteObject::new(handle, objects.insert(object)))
+        })
+    }
+    fn submit<T, F>(&self, f: F) -> Response<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Objects) -> Result<T> + Send + 'static,
+    {
+        let (job, response) = job(f);
+        if !self.shared.post(job) {
+            response
+                .slot
+                .complete(Err(Error::Generic("STA worker is shut down")));
+        } else if let Some(timeout) = self.timeout {
+            let expire = Arc::downgrade(&response.slot);
+            self.shared.timer.add(Instant::now() + timeout, expire);
+        }
+        response
+    }
+}
+
+/// Wrap `f` in a job that completes the returned response when it runs, or
+/// with a shutdown error when it is dropped unrun.
+fn job<T, F>(f: F) -> (Job, Response<T>)
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Objects) -> Result<T> + Send + 'static,
+{
+    let slot = Arc::new(Slot::default());
+    let completer = Completer { slot: slot.clone() };
+    let job: Job = Box::new(move |objects| {
+        if completer.slot.is_complete() {
+            // Timed out while queued.
+            return;
+        }
+        completer.complete(f(objects));
+    });
+    (job, Response { slot })
+}
+
+impl fmt::Debug for WorkerHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("WorkerHandle")
+            .field("thread_id", &self.shared.thread_id)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// An object held by an [`StaWorker`]. The object is released on the worker
+/// thread once every clone of the handle is dropped.
+#[derive(Clone)]
+pub struct RemoteObject {
+    handle: WorkerHandle,
+    object: Arc<ObjectRef>,
+}
+
+struct ObjectRef {
+    shared: Arc<Shared>,
+    id: u64,
+}
+
+impl Drop for ObjectRef {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.shared.post(Box::new(move |objects| {
+            objects.objects.remove(&id);
+        }));
+    }
+}
+
+impl RemoteObject {
+    fn new(handle: WorkerHandle, id: u64) -> RemoteObject {
+        let object = Arc::new(ObjectRef {
+            shared: handle.shared.clone(),
+            id,
+        });
+        RemoteObject { handle, object }
+    }
+    pub fn id(&self) -> u64 {
+        self.object.id
+    }
+    pub fn handle(&self) -> &WorkerHandle {
+        &self.handle
+    }
+    pub fn with_timeout(&self, timeout: Duration) -> RemoteObject {
+        RemoteObject {
+            handle: self.handle.with_timeout(timeout),
+            object: self.object.clone(),
+        }
+    }
+    /// Evaluate a property path, see [`OleData::navigate`].
+    ///
+    pub fn get<S: Into<String>>(&self, path: S) -> Response<RemoteValue> {
+        let path = path.into();
+        let (id, handle) = (self.id(), self.handle.clone());
+        self.handle.submit(move |objects| {
+            let value = objects.get(id)?.navigate(&path)?;
+            Ok(RemoteValue::from_local(value, objects, handle))
+        })
+    }
+    /// Call the last member of `path` with `args`, e.g.
+    /// `excel.call("Workbooks.Open", vec!["C:\\book.xlsx".into()])`.
+    ///
+    pub fn call<S: Into<String>>(&self, path: S, args: Vec<RemoteValue>) -> Response<RemoteValue> {
+        let path = path.into();
+        let (id, handle) = (self.id(), self.handle.clone());
+        self.handle.submit(move |objects| {
+            let (target, name) = resolve_target(objects.get(id)?, &path)?;
+            let args = args
+                .iter()
+                .map(|arg| arg.to_variant(objects))
+                .collect::<Result<Vec<_>>>()?;
+            let dispid = target.dispid(name)?;
+            let result =
+                target.call_dispid(dispid, args, DISPATCH_METHOD | DISPATCH_PROPERTYGET)?;
+            let value = OleValue::try_from(result)?;
+            Ok(RemoteValue::from_local(value, objects, handle))
+        })
+    }
+    /// Set the property named by the last member of `path`.
+    ///
+    pub fn put<S: Into<String>>(&self, path: S, value: RemoteValue) -> Response<()> {
+        let path = path.into();
+        let id = self.id();
+        self.handle.submit(move |objects| {
+            let (target, name) = resolve_target(objects.get(id)?, &path)?;
+            let mut value = value.to_variant(objects)?;
+            target.set(name, &mut value)
+        })
+    }
+    /// Register the object in the Global Interface Table so that it can be
+    /// used directly from another thread.
+    ///
+    pub fn to_agile(&self) -> Response<AgileOleData> {
+        let id = self.id();
+        self.handle
+            .submit(move |objects| AgileOleData::new(objects.get(id)?))
+    }
+}
+
+impl fmt::Debug for RemoteObject {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RemoteObject")
+            .field("id", &self.object.id)
+            .finish()
+    }
+}
+
+/// Split `path` into the object its last member is called on and that member.
+fn resolve_target<'p>(object: &OleData, path: &'p str) -> Result<(OleData, &'p str)> {
+    match path.rsplit_once('.') {
+        None => Ok((object.clone(), path)),
+        Some((parent, name)) => match object.navigate(parent)? {
+            OleValue::Object(target) => Ok((target, name)),
+            value => Err(Error::Custom(format!(
+                "`{parent}` returned {value} instead of an object"
+            ))),
+        },
+    }
+}
+
+/// A value passed to or returned from an [`StaWorker`]. Objects stay on the
+/// worker thread and are represented by a [`RemoteObject`].
+#[derive(Debug, Clone)]
+pub struct RemoteValue(RemoteValueInner);
+
+#[derive(Debug, Clone)]
+enum RemoteValueInner {
+    Value(OleValue),
+    Object(RemoteObject),
+}
+
+// `RemoteValueInner::Value` never holds `OleValue::Object` or
+// `OleValue::Unknown`, so no apartment-bound interface crosses threads.
+unsafe impl Send for RemoteValue {}
+
+impl RemoteValue {
+    /// Wrap a plain value. Objects are rejected: use a [`RemoteObject`] instead.
+    ///
+    pub fn value(value: OleValue) -> Result<RemoteValue> {
+        match value {
+            OleValue::Object(_) | OleValue::Unknown(_) => Err(Error::Generic(
+                "objects must be passed to the STA worker as RemoteObject",
+            )),
+            value => Ok(RemoteValue(RemoteValueInner::Value(value))),
+        }
+    }
+    pub fn as_value(&self) -> Option<&OleValue> {
+        match &self.0 {
+            RemoteValueInner::Value(value) => Some(value),
+            RemoteValueInner::Object(_) => None,
+        }
+    }
+    pub fn into_value(self) -> Option<OleValue> {
+        match self.0 {
+            RemoteValueInner::Value(value) => Some(value),
+            RemoteValueInner::Object(_) => None,
+        }
+    }
+    pub fn as_object(&self) -> Option<&RemoteObject> {
+        match &self.0 {
+            RemoteValueInner::Object(object) => Some(object),
+            RemoteValueInner::Value(_) => None,
+        }
+    }
+    pub fn into_object(self) -> Option<RemoteObject> {
+        match self.0 {
+            RemoteValueInner::Object(object) => Some(object),
+            RemoteValueInner::Value(_) => None,
+        }
+    }
+    fn from_local(value: OleValue, objects: &mut Objects, handle: WorkerHandle) -> RemoteValue {
+        match value {
+            OleValue::Object(object) => {
+                let id = objects.insert(object);
+                RemoteValue(RemoteValueInner::Object(RemoteObject::new(handle, id)))
+            }
+            // Not usable across threads, and not an `IDispatch` a `RemoteObject`
+            // could wrap; the caller sees `Nothing`.
+            OleValue::Unknown(_) => RemoteValue(RemoteValueInner::Value(OleValue::Nothing)),
+            value => RemoteValue(RemoteValueInner::Value(value)),
+        }
+    }
+    fn to_variant(&self, objects: &Objects) -> Result<VARIANT> {
+        match &self.0 {
+            RemoteValueInner::Value(value) => Ok(VARIANT::from(value)),
+            RemoteValueInner::Object(object) => {
+                Ok(VARIANT::from(objects.get(object.id())?.dispatch.clone()))
+            }
+        }
+    }
+}
+
+impl From<RemoteObject> for RemoteValue {
+    fn from(object: RemoteObject) -> Self {
+        RemoteValue(RemoteValueInner::Object(object))
+    }
+}
+
+impl From<bool> for RemoteValue {
+    fn from(value: bool) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::Bool(value)))
+    }
+}
+
+impl From<i32> for RemoteValue {
+    fn from(value: i32) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::I4(value)))
+    }
+}
+
+impl From<i64> for RemoteValue {
+    fn from(value: i64) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::I8(value)))
+    }
+}
+
+impl From<f64> for RemoteValue {
+    fn from(value: f64) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::R8(value)))
+    }
+}
+
+impl From<String> for RemoteValue {
+    fn from(value: String) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::String(value)))
+    }
+}
+
+impl From<&str> for RemoteValue {
+    fn from(value: &str) -> Self {
+        RemoteValue(RemoteValueInner::Value(OleValue::String(value.to_string())))
+    }
+}
+
+/// The pending result of a request queued on an [`StaWorker`].
+///
+/// Await it from async code, or block on it with [`Response::wait`].
+pub struct Response<T> {
+    slot: Arc<Slot<T>>,
+}
+
+impl<T> Response<T> {
+    /// Block the current thread until the response arrives.
+    ///
+    pub fn wait(self) -> Result<T> {
+        let mut state = self.slot.state.lock().unwrap();
+        loop {
+            if let Some(result) = state.result.take() {
+                return result;
+            }
+            state = self.slot.ready.wait(state).unwrap();
+        }
+    }
+}
+
+impl<T> Future for Response<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.slot.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct Slot<T> {
+    state: Mutex<SlotState<T>>,
+    ready: Condvar,
+}
+
+struct SlotState<T> {
+    result: Option<Result<T>>,
+    complete: bool,
+    waker: Option<Waker>,
+}
+
+impl<T> Default for Slot<T> {
+    fn default() -> Self {
+        Slot {
+            state: Mutex::new(SlotState {
+                result: None,
+                complete: false,
+                waker: None,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+}
+
+impl<T> Slot<T> {
+    /// Store the result unless one has already been stored; the first of the
+    /// worker, the timeout and a shutdown wins.
+    fn complete(&self, result: Result<T>) {
+        let mut state = self.state.lock().unwrap();
+        if state.complete {
+            return;
+        }
+        state.complete = true;
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.ready.notify_all();
+    }
+    fn is_complete(&self) -> bool {
+        self.state.lock().unwrap().complete
+    }
+}
+
+impl<T: Send> Expire for Slot<T> {
+    fn expire(&self) {
+        self.complete(Err(Error::Generic("STA worker request timed out")));
+    }
+}
+
+/// Completes the slot with a shutdown error if the job is dropped unrun.
+struct Completer<T> {
+    slot: Arc<Slot<T>>,
+}
+
+impl<T> Completer<T> {
+    fn complete(&self, result: Result<T>) {
+        self.slot.complete(result);
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        self.slot.complete(Err(Error::Generic(
+            "STA worker shut down before running the request",
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn error_message<T: fmt::Debug>(result: Result<T>) -> &'static str {
+        match result {
+            Err(Error::Generic(message)) => message,
+            other => panic!("expected a generic error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn timer_fails_a_pending_request() {
+        let timer = Arc::new(Timer::default());
+        let (_job, response) = job(|_| Ok(1));
+        let expire = Arc::downgrade(&response.slot);
+        timer.add(Instant::now() + Duration::from_millis(10), expire);
+        assert_eq!(
+            error_message(response.wait()),
+            "STA worker request timed out"
+        );
+        timer.stop();
+    }
+
+    #[test]
+    fn timer_orders_deadlines() {
+        let timer = Arc::new(Timer::default());
+        let (_late_job, late) = job(|_| Ok(1));
+        let (_early_job, early) = job(|_| Ok(2));
+        let expire = Arc::downgrade(&late.slot);
+        timer.add(Instant::now() + Duration::from_secs(60), expire);
+        let expire = Arc::downgrade(&early.slot);
+        timer.add(Instant::now() + Duration::from_millis(10), expire);
+        assert_eq!(error_message(early.wait()), "STA worker request timed out");
+        assert!(!late.slot.is_complete());
+        timer.stop();
+    }
+
+    #[test]
+    fn completion_before_timeout_wins() {
+        let (job, response) = job(|_| Ok(1));
+        job(&mut Objects::default());
+        response.slot.expire();
+        assert_eq!(response.wait().unwrap(), 1);
+    }
+
+    #[test]
+    fn timeout_before_run_skips_the_request() {
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = ran.clone();
+        let (job, response) = job(move |_| {
+            flag.store(true, Ordering::SeqCst);
+            Ok(1)
+        });
+        response.slot.expire();
+        job(&mut Objects::default());
+        assert!(!ran.load(Ordering::SeqCst));
+        assert_eq!(
+            error_message(response.wait()),
+            "STA worker request timed out"
+        );
+    }
+
+    #[test]
+    fn shutdown_fails_queued_requests() {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let responses: Vec<Response<i32>> = (0..3)
+            .map(|i| {
+                let (job, response) = job(move |_| Ok(i));
+                sender.send(job).unwrap();
+                response
+            })
+            .collect();
+        drop(receiver);
+        for response in responses {
+            assert_eq!(
+                error_message(response.wait()),
+                "STA worker shut down before running the request"
+            );
+        }
+    }
+}